@@ -0,0 +1,144 @@
+use ethers::prelude::*;
+use ethers::types::{H160, H256, U256, U64};
+use dotenv::dotenv;
+use anyhow::anyhow;
+use std::sync::Arc;
+
+use super::utils::local_signer::get_local_signer;
+use super::utils::contract_addresses::Contracts;
+use super::utils::structs::{CancelledOrders, KeyHashes, Markets, Order, OrderType, TransactionSettings};
+use super::utils::reader_interface::reader_functions::{get_account_orders, get_order};
+use super::utils::gas_calculator::get_data_store_uint;
+use super::utils::order_events::get_cancelled_order_keys_from_receipt;
+use super::utils::transaction_sender::{send_eip1559_transaction, SignerClient};
+
+use crate::contract_caller::connect_provider::connect_provider;
+
+
+pub async fn cancel_order(order_key: H256) -> Result<CancelledOrders, Box<dyn std::error::Error>> {
+    let order: Order = get_order(order_key).await?
+        .ok_or("No pending order found for this key")?;
+
+    let (cancellable, skipped): (Vec<Order>, Vec<Order>) = split_cancellable_orders(vec![order]).await?;
+    if !skipped.is_empty() {
+        return Err("Market order is still inside the request expiration window and cannot be cancelled yet".into());
+    }
+
+    submit_cancellations(cancellable, Vec::new()).await
+}
+
+// Cancels every pending order for the account, optionally only those on a single market.
+// Only the order's own account may cancel it, so the account must be the local signer.
+pub async fn cancel_all_orders(account: H160, market_filter: Option<Markets>) -> Result<CancelledOrders, Box<dyn std::error::Error>> {
+    let account_orders: Vec<Order> = get_account_orders(account).await?;
+    println!("Found {} pending orders for {:?}", account_orders.len(), account);
+
+    let orders: Vec<Order> = account_orders
+        .into_iter()
+        .filter(|order| market_filter.is_none_or(|market| order.market == Some(market)))
+        .collect();
+
+    // A single order that is not yet cancellable would revert the whole multicall, so those are left out
+    let (cancellable, skipped): (Vec<Order>, Vec<Order>) = split_cancellable_orders(orders).await?;
+    let skipped_order_keys: Vec<H256> = skipped.iter().map(|order| order.key).collect();
+    if !skipped_order_keys.is_empty() {
+        println!("Skipping market orders inside the request expiration window: {:?}", skipped_order_keys);
+    }
+
+    if cancellable.is_empty() {
+        println!("No pending orders to cancel");
+        return Ok(CancelledOrders {
+            order_keys: Vec::new(),
+            skipped_order_keys,
+            refunded_execution_fee: U256::zero(),
+            receipt: None,
+        });
+    }
+
+    submit_cancellations(cancellable, skipped_order_keys).await
+}
+
+// Users can only cancel a market order once REQUEST_EXPIRATION_BLOCK_AGE blocks have passed since it was
+// last updated, before that ExchangeRouter.cancelOrder reverts with RequestNotYetCancellable.
+// Returns the orders that can be cancelled now and the ones that have to wait.
+async fn split_cancellable_orders(orders: Vec<Order>) -> Result<(Vec<Order>, Vec<Order>), Box<dyn std::error::Error>> {
+    if !orders.iter().any(is_market_order) {
+        return Ok((orders, Vec::new()));
+    }
+
+    let provider: Provider<Http> = connect_provider().await?;
+    let current_block: U64 = provider.get_block_number().await?;
+    let contracts: Contracts = Contracts::new(Arc::new(provider));
+    let keys: KeyHashes = KeyHashes::new();
+    let request_expiration_block_age: U256 = get_data_store_uint(&contracts, &keys.request_expiration_block_age).await?;
+
+    let (cancellable, skipped): (Vec<Order>, Vec<Order>) = orders
+        .into_iter()
+        .partition(|order| {
+            !is_market_order(order)
+                || U256::from(current_block.as_u64()) >= order.updated_at_block + request_expiration_block_age
+        });
+
+    Ok((cancellable, skipped))
+}
+
+fn is_market_order(order: &Order) -> bool {
+    matches!(order.order_type, OrderType::MarketSwap | OrderType::MarketIncrease | OrderType::MarketDecrease)
+}
+
+// Cancels all the given orders in a single ExchangeRouter multicall
+async fn submit_cancellations(orders: Vec<Order>, skipped_order_keys: Vec<H256>) -> Result<CancelledOrders, Box<dyn std::error::Error>> {
+    dotenv().ok();
+
+    let wallet = get_local_signer()?;
+    let provider: Provider<Http> = connect_provider().await?;
+    let arc_provider: Arc<Provider<Http>> = Arc::new(provider);
+    let contracts: Contracts = Contracts::new(arc_provider.clone());
+    let client: SignerClient = SignerMiddleware::new(arc_provider.clone(), wallet.clone());
+
+    let mut bundle: Vec<Bytes> = Vec::new();
+
+    for order in &orders {
+        if order.account != wallet.address() {
+            return Err(format!("Order {:?} belongs to a different account", order.key).into());
+        }
+
+        let cancel_bytes: Bytes = contracts.exchange_router_contract
+            .cancel_order(order.key.to_fixed_bytes())
+            .calldata()
+            .ok_or_else(|| anyhow!("Failed to build cancelOrder calldata"))?;
+        bundle.push(cancel_bytes);
+    }
+
+    println!("Cancelling orders: {:?}", orders.iter().map(|order| order.key).collect::<Vec<H256>>());
+
+    let exchange_router_address: H160 = contracts.exchange_router_contract.address();
+    let tx_data: Bytes = contracts.exchange_router_contract.multicall(bundle).calldata()
+    .ok_or_else(|| anyhow!("Failed to build multicall calldata"))?;
+    let receipt: Option<TransactionReceipt> = send_eip1559_transaction(&client, exchange_router_address, tx_data, U256::zero(), &TransactionSettings::default()).await?;
+
+    println!("Cancel Tx submitted to network, receipt: {:?}", receipt);
+
+    let receipt: TransactionReceipt = receipt.ok_or("Cancel transaction was dropped before confirmation")?;
+    if receipt.status != Some(U64::one()) {
+        return Err(format!("Cancel transaction {:?} reverted", receipt.transaction_hash).into());
+    }
+
+    // Only orders the EventEmitter confirms as cancelled count. The signer acts as the keeper on a user
+    // cancel, so the keeper share and the refund of each cancelled order's fee both come back to the account.
+    let order_keys: Vec<H256> = get_cancelled_order_keys_from_receipt(&receipt);
+    let refunded_execution_fee: U256 = orders
+        .iter()
+        .filter(|order| order_keys.contains(&order.key))
+        .fold(U256::zero(), |total, order| total + order.execution_fee);
+
+    println!("Cancelled orders: {:?}", order_keys);
+    println!("Refunded execution fee: {}", refunded_execution_fee);
+
+    Ok(CancelledOrders {
+        order_keys,
+        skipped_order_keys,
+        refunded_execution_fee,
+        receipt: Some(receipt),
+    })
+}
@@ -1,5 +1,7 @@
 pub mod get_abi;
 pub mod connect_provider;
 pub mod sol_call;
+pub mod update_order;
+pub mod cancel_order;
 pub mod utils;
 pub mod order_builder;
\ No newline at end of file
@@ -0,0 +1,37 @@
+use crate::contract_caller::utils::constants::BPS_DIVISOR;
+use crate::contract_caller::utils::structs::TokenPriceFromApiResponse;
+use ethers::types::U256;
+
+// Buying the index token (long increase, short decrease) fills at the max price, so we accept up to
+// max price + slippage. Selling it (short increase, long decrease) fills at the min price, so we
+// accept down to min price - slippage.
+pub fn calculate_acceptable_price(price_output: &TokenPriceFromApiResponse, is_long: bool, is_increase: bool, slippage_bps: u32) -> Result<U256, Box<dyn std::error::Error>> {
+    let is_buying: bool = is_long == is_increase;
+    let oracle_price: U256 = if is_buying {
+        U256::from_dec_str(&price_output.max_price_full)?
+    } else {
+        U256::from_dec_str(&price_output.min_price_full)?
+    };
+
+    apply_slippage(oracle_price, is_buying, slippage_bps)
+}
+
+// Same direction rules as calculate_acceptable_price, but around a fixed reference such as a trigger price
+pub fn apply_slippage(price: U256, is_buying: bool, slippage_bps: u32) -> Result<U256, Box<dyn std::error::Error>> {
+    if slippage_bps >= BPS_DIVISOR {
+        return Err("Slippage must be below 10000 bps".into());
+    }
+
+    let slippage_factor: u32 = if is_buying {
+        BPS_DIVISOR + slippage_bps
+    } else {
+        BPS_DIVISOR - slippage_bps
+    };
+
+    let adjusted_price: U256 = price
+        .checked_mul(U256::from(slippage_factor))
+        .ok_or("Slippage application error")?
+        / U256::from(BPS_DIVISOR);
+
+    Ok(adjusted_price)
+}
@@ -1,9 +1,55 @@
-pub fn calculate_leveraged_amount(amount: String, leverage_factor: f32) -> Result<u64, Box<dyn std::error::Error>> {
-    if leverage_factor >= 50.0 {
-        return Err("Leverage factor must be less than 50".into());
+use crate::contract_caller::utils::structs::{PositionSizing, SimpleOrder, TokenPriceFromApiResponse};
+use crate::contract_caller::order_builder::get_price::fetch_token_price;
+use crate::contract_caller::utils::constants::BPS_DIVISOR;
+use ethers::types::U256;
+
+// Oracle prices are USD per smallest token unit with 30 decimals, so amount * price is already
+// the collateral value in GMX's 30 decimal USD precision with no rounding
+pub fn calculate_collateral_usd_value(collateral_amount: U256, collateral_price: U256) -> Result<U256, Box<dyn std::error::Error>> {
+    let usd_value: U256 = collateral_amount
+        .checked_mul(collateral_price)
+        .ok_or("Collateral USD value overflow")?;
+
+    Ok(usd_value)
+}
+
+// Leverage is applied in basis points so fractional factors like 2.5x keep their precision
+pub fn apply_leverage(usd_value: U256, leverage_factor: f32) -> Result<U256, Box<dyn std::error::Error>> {
+    if leverage_factor <= 0.0 || leverage_factor > 50.0 {
+        return Err("Leverage factor must be greater than 0 and cannot exceed 50".into());
     }
 
-    let amount_int: u64 = amount.parse()?;
-    let leveraged_amount: f32 = amount_int as f32 * leverage_factor;
-    Ok(leveraged_amount as u64)
-}
\ No newline at end of file
+    let leverage_bps: u64 = (leverage_factor as f64 * BPS_DIVISOR as f64).round() as u64;
+    let leveraged_value: U256 = usd_value
+        .checked_mul(U256::from(leverage_bps))
+        .ok_or("Leverage application error")?
+        / U256::from(BPS_DIVISOR);
+
+    Ok(leveraged_value)
+}
+
+// Values the collateral at its min oracle price so the position is never sized larger than intended
+pub async fn calculate_position_sizing(input: &SimpleOrder) -> Result<PositionSizing, Box<dyn std::error::Error>> {
+    let collateral_amount: U256 = U256::from_dec_str(&input.collateral_amount)
+        .map_err(|e| format!("Error parsing collateral_amount to U256: {}", e))?;
+    let collateral_price_output: TokenPriceFromApiResponse = fetch_token_price(input.collateral_token.clone()).await?;
+    let collateral_price: U256 = U256::from_dec_str(&collateral_price_output.min_price_full)?;
+
+    let collateral_value_usd: U256 = calculate_collateral_usd_value(collateral_amount, collateral_price)?;
+    let size_delta_usd: U256 = apply_leverage(collateral_value_usd, input.leverage_factor)?;
+
+    println!("Collateral value: ${}, position size: ${}", format_usd(collateral_value_usd), format_usd(size_delta_usd));
+
+    Ok(PositionSizing {
+        collateral_amount,
+        collateral_price,
+        collateral_value_usd,
+        size_delta_usd,
+    })
+}
+
+// Renders a 30 decimal USD value with cents, e.g. 1234500000...0 -> "1234.50"
+pub fn format_usd(usd_value: U256) -> String {
+    let cents: U256 = usd_value / U256::exp10(28);
+    format!("{}.{:02}", cents / 100, (cents % 100).as_u64())
+}
@@ -0,0 +1,255 @@
+use ethers::abi::Token as AbiToken;
+use ethers::prelude::{Http, Provider};
+use ethers::types::{Address, H160, H256, I256, U256};
+use std::sync::Arc;
+
+use crate::contract_caller::connect_provider::connect_provider;
+use crate::contract_caller::order_builder::calculate_leverage::calculate_position_sizing;
+use crate::contract_caller::order_builder::get_price::fetch_token_price;
+use crate::contract_caller::utils::constants::FLOAT_PRECISION;
+use crate::contract_caller::utils::contract_addresses::Contracts;
+use crate::contract_caller::utils::gas_calculator::get_data_store_uint;
+use crate::contract_caller::utils::hash_utils::hash_scoped_key;
+use crate::contract_caller::utils::reader_interface::reader_functions::get_position_info;
+use crate::contract_caller::utils::structs::{KeyHashes, LiquidationInputs, MarketInfo, Markets, Position, PositionInfo, PositionSizing, SimpleOrder, Token};
+
+// A position is liquidatable once its remaining collateral (collateral + PnL + close price impact - fees) drops
+// below max(size * MIN_COLLATERAL_FACTOR, MIN_COLLATERAL_USD), as in PositionUtils.isPositionLiquidatable.
+// Positive price impact is ignored there, so only a negative impact is added to the fees.
+// Solving that for the index price gives the liquidation price. None means no positive price liquidates
+// the position, e.g. a short backed by more index tokens than it is short.
+// Only index token collateral moves with the price. Any other collateral is held at collateral_usd, which is
+// exact for stablecoins but ignores its own price moves for volatile collateral such as ETH backing a BTC
+// position, so the result there only holds while the collateral token's price is unchanged.
+pub fn calculate_liquidation_price(inputs: &LiquidationInputs) -> Option<U256> {
+    let min_collateral_usd_for_leverage: U256 = inputs.size_in_usd * inputs.min_collateral_factor / U256::exp10(FLOAT_PRECISION);
+    let threshold: I256 = I256::from_raw(min_collateral_usd_for_leverage.max(inputs.min_collateral_usd));
+    let size_in_usd: I256 = I256::from_raw(inputs.size_in_usd);
+    let size_in_tokens: I256 = I256::from_raw(inputs.size_in_tokens);
+    let fees: I256 = I256::from_raw(inputs.pending_fees_usd) - inputs.close_price_impact_usd.min(I256::zero());
+
+    // Long PnL is size_in_tokens * price - size_in_usd, short PnL is the reverse
+    let (numerator, denominator): (I256, I256) = if inputs.collateral_is_index_token {
+        let collateral_amount: I256 = I256::from_raw(inputs.collateral_amount);
+        if inputs.is_long {
+            (threshold + size_in_usd + fees, collateral_amount + size_in_tokens)
+        } else {
+            (size_in_usd - fees - threshold, size_in_tokens - collateral_amount)
+        }
+    } else {
+        let collateral_usd: I256 = I256::from_raw(inputs.collateral_usd);
+        if inputs.is_long {
+            (threshold + size_in_usd + fees - collateral_usd, size_in_tokens)
+        } else {
+            (collateral_usd + size_in_usd - fees - threshold, size_in_tokens)
+        }
+    };
+
+    if numerator <= I256::zero() || denominator <= I256::zero() {
+        return None;
+    }
+
+    Some((numerator / denominator).into_raw())
+}
+
+// Liquidation price of an open position, with its pending borrowing, funding and close fees taken into account
+pub async fn get_liquidation_price(position_key: H256) -> Result<Option<U256>, Box<dyn std::error::Error>> {
+    let position_info: PositionInfo = get_position_info(position_key).await?;
+    let position: &Position = &position_info.position;
+
+    let market: Markets = Markets::from_address(position.market)
+        .ok_or(format!("Unsupported market {:?}", position.market))?;
+    let collateral_token: Token = Token::from_address(position.collateral_token)
+        .ok_or("Unsupported collateral token")?;
+    let (min_collateral_factor, min_collateral_usd): (U256, U256) = get_min_collateral_params(position.market).await?;
+
+    let inputs: LiquidationInputs = LiquidationInputs {
+        is_long: position.is_long,
+        size_in_usd: position.size_in_usd,
+        size_in_tokens: position.size_in_tokens,
+        collateral_is_index_token: is_index_token_collateral(&market, collateral_token.info().name),
+        collateral_amount: position.collateral_amount,
+        collateral_usd: position_info.collateral_usd,
+        pending_fees_usd: position_info.fees.total_cost_usd,
+        close_price_impact_usd: position_info.price_impact_usd,
+        min_collateral_factor,
+        min_collateral_usd,
+    };
+
+    Ok(calculate_liquidation_price(&inputs))
+}
+
+// Liquidation price the order would open at, before it is placed. The entry is priced at the oracle
+// price the order would fill at, and the fees are the position fee for opening and for closing.
+// The close price impact depends on the pool at close time, so it is left out here.
+pub async fn get_liquidation_price_for_order(input: &SimpleOrder) -> Result<Option<U256>, Box<dyn std::error::Error>> {
+    let market: Markets = Markets::from_token_name(&input.index_token)
+        .ok_or("Market not found for index token")?;
+    let market_address: H160 = market.info().market_address.parse()
+        .map_err(|e| format!("Error parsing market address: {}", e))?;
+
+    let sizing: PositionSizing = calculate_position_sizing(input).await?;
+
+    // Longs buy at the max price and shorts sell at the min price
+    let price_output = fetch_token_price(input.index_token.clone()).await?;
+    let entry_price: U256 = if input.is_long {
+        U256::from_dec_str(&price_output.max_price_full)?
+    } else {
+        U256::from_dec_str(&price_output.min_price_full)?
+    };
+    let size_in_tokens: U256 = sizing.size_delta_usd.checked_div(entry_price)
+        .ok_or(format!("Oracle price for {} is zero", input.index_token))?;
+
+    // Collateral other than USDC is swapped through its own market on the way in, so the position holds the swap output
+    let swap_path: Vec<Markets> = Markets::get_swap_path_for_collateral(&input.collateral_token)
+        .iter()
+        .filter_map(|address| address.parse::<Address>().ok())
+        .filter_map(Markets::from_address)
+        .collect();
+    let position_collateral: String = Markets::get_swap_output_token(&input.collateral_token, &swap_path)
+        .ok_or("Collateral cannot be routed into the market")?;
+
+    let (min_collateral_factor, min_collateral_usd): (U256, U256) = get_min_collateral_params(market_address).await?;
+    let position_fee_factor: U256 = get_position_fee_factor(market_address).await?;
+    let position_fees_usd: U256 = sizing.size_delta_usd * position_fee_factor / U256::exp10(FLOAT_PRECISION) * U256::from(2);
+
+    let inputs: LiquidationInputs = LiquidationInputs {
+        is_long: input.is_long,
+        size_in_usd: sizing.size_delta_usd,
+        size_in_tokens,
+        collateral_is_index_token: is_index_token_collateral(&market, &position_collateral),
+        collateral_amount: sizing.collateral_amount,
+        collateral_usd: sizing.collateral_value_usd,
+        pending_fees_usd: position_fees_usd,
+        close_price_impact_usd: I256::zero(),
+        min_collateral_factor,
+        min_collateral_usd,
+    };
+
+    Ok(calculate_liquidation_price(&inputs))
+}
+
+// Synthetic markets are backed by other tokens, so only a non-synthetic market's own long token tracks the index
+fn is_index_token_collateral(market: &Markets, collateral_token: &str) -> bool {
+    let info: MarketInfo = market.info();
+    info.is_synthetic == Some(false) && info.long_token == collateral_token
+}
+
+// MIN_COLLATERAL_FACTOR is set per market, MIN_COLLATERAL_USD is global
+async fn get_min_collateral_params(market: H160) -> Result<(U256, U256), Box<dyn std::error::Error>> {
+    let provider: Provider<Http> = connect_provider().await?;
+    let contracts: Contracts = Contracts::new(Arc::new(provider));
+    let keys: KeyHashes = KeyHashes::new();
+
+    let min_collateral_factor_key: String = get_market_key(&keys.min_collateral_factor, market, None);
+    let min_collateral_factor: U256 = get_data_store_uint(&contracts, &min_collateral_factor_key).await?;
+    let min_collateral_usd: U256 = get_data_store_uint(&contracts, &keys.min_collateral_usd).await?;
+    println!("Min collateral factor: {}, min collateral USD: {}", min_collateral_factor, min_collateral_usd);
+
+    Ok((min_collateral_factor, min_collateral_usd))
+}
+
+// Uses the factor for negative price impact, which is the higher of the two
+async fn get_position_fee_factor(market: H160) -> Result<U256, Box<dyn std::error::Error>> {
+    let provider: Provider<Http> = connect_provider().await?;
+    let contracts: Contracts = Contracts::new(Arc::new(provider));
+    let keys: KeyHashes = KeyHashes::new();
+
+    let position_fee_factor_key: String = get_market_key(&keys.position_fee_factor, market, Some(false));
+    let position_fee_factor: U256 = get_data_store_uint(&contracts, &position_fee_factor_key).await?;
+
+    Ok(position_fee_factor)
+}
+
+fn get_market_key(base_key_hex: &str, market: H160, flag: Option<bool>) -> String {
+    match flag {
+        Some(flag) => hash_scoped_key(base_key_hex, vec!["address", "bool"], vec![AbiToken::Address(market), AbiToken::Bool(flag)]),
+        None => hash_scoped_key(base_key_hex, vec!["address"], vec![AbiToken::Address(market)]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // A 10,000 USD position on an 18 decimals token opened at 2,000 USD: 5 tokens, 10 USD of fees,
+    // a 1% MIN_COLLATERAL_FACTOR and a 5 USD MIN_COLLATERAL_USD, so the threshold is 100 USD
+    fn inputs(is_long: bool, collateral_is_index_token: bool, collateral_amount: U256, collateral_usd: U256) -> LiquidationInputs {
+        LiquidationInputs {
+            is_long,
+            size_in_usd: usd(10_000),
+            size_in_tokens: U256::from(5) * U256::exp10(18),
+            collateral_is_index_token,
+            collateral_amount,
+            collateral_usd,
+            pending_fees_usd: usd(10),
+            close_price_impact_usd: I256::zero(),
+            min_collateral_factor: U256::exp10(28),
+            min_collateral_usd: usd(5),
+        }
+    }
+
+    fn usd(amount: u64) -> U256 {
+        U256::from(amount) * U256::exp10(FLOAT_PRECISION)
+    }
+
+    // USD price of one whole 18 decimals token, in contract precision
+    fn token_price(amount: u64) -> U256 {
+        U256::from(amount) * U256::exp10(12)
+    }
+
+    fn half_token() -> U256 {
+        U256::from(5) * U256::exp10(17)
+    }
+
+    #[test]
+    fn long_with_stable_collateral() {
+        // 1,000 + (5 * 1,822 - 10,000) - 10 = 100
+        let liquidation_price: Option<U256> = calculate_liquidation_price(&inputs(true, false, U256::zero(), usd(1_000)));
+        assert_eq!(liquidation_price, Some(token_price(1_822)));
+    }
+
+    #[test]
+    fn short_with_stable_collateral() {
+        // 1,000 + (10,000 - 5 * 2,178) - 10 = 100
+        let liquidation_price: Option<U256> = calculate_liquidation_price(&inputs(false, false, U256::zero(), usd(1_000)));
+        assert_eq!(liquidation_price, Some(token_price(2_178)));
+    }
+
+    #[test]
+    fn long_with_index_token_collateral() {
+        // 0.5 * p + (5 * p - 10,000) - 10 = 100, so p = 10,110 / 5.5
+        let liquidation_price: Option<U256> = calculate_liquidation_price(&inputs(true, true, half_token(), U256::zero()));
+        assert_eq!(liquidation_price, Some(U256::from(1_838_181_818_181_818u64)));
+    }
+
+    #[test]
+    fn short_with_index_token_collateral() {
+        // 0.5 * p + (10,000 - 5 * p) - 10 = 100, so p = 9,890 / 4.5
+        let liquidation_price: Option<U256> = calculate_liquidation_price(&inputs(false, true, half_token(), U256::zero()));
+        assert_eq!(liquidation_price, Some(U256::from(2_197_777_777_777_777u64)));
+    }
+
+    #[test]
+    fn short_fully_backed_by_index_token_is_never_liquidated() {
+        let collateral_amount: U256 = U256::from(5) * U256::exp10(18);
+        assert_eq!(calculate_liquidation_price(&inputs(false, true, collateral_amount, U256::zero())), None);
+    }
+
+    #[test]
+    fn long_with_more_collateral_than_size_is_never_liquidated() {
+        assert_eq!(calculate_liquidation_price(&inputs(true, false, U256::zero(), usd(20_000))), None);
+    }
+
+    #[test]
+    fn only_negative_close_price_impact_counts() {
+        let mut long: LiquidationInputs = inputs(true, false, U256::zero(), usd(1_000));
+
+        // A 90 USD impact costs as much as 90 USD of extra fees: 1,000 + (5 * 1,840 - 10,000) - 10 - 90 = 100
+        long.close_price_impact_usd = -I256::from_raw(usd(90));
+        assert_eq!(calculate_liquidation_price(&long), Some(token_price(1_840)));
+
+        long.close_price_impact_usd = I256::from_raw(usd(90));
+        assert_eq!(calculate_liquidation_price(&long), Some(token_price(1_822)));
+    }
+}
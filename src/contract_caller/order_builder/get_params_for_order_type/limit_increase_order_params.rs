@@ -0,0 +1,62 @@
+use crate::contract_caller::utils::structs::{MarketIncreaseOrderCalcOutput, PositionSizing, Token, TokenInfo, AddressesForMarketIncreaseOrder, OrderObject, OrderType, Markets, SimpleOrder, SimpleLimitOrder};
+use crate::contract_caller::order_builder::get_params_for_order_type::market_increase_order_params::{get_addresses_for_market_increase_order, create_full_order_object};
+use crate::contract_caller::order_builder::calculate_leverage::calculate_position_sizing;
+use crate::contract_caller::order_builder::get_price::scale_usd_price_to_contract_price;
+use crate::contract_caller::order_builder::calculate_acceptable_price::apply_slippage;
+use crate::contract_caller::utils::gas_calculator::{calculate_execution_fee, RESTING_ORDER_FEE_MULTIPLIER_BPS};
+use ethers::types::U256;
+
+pub async fn calculate_limit_increase_order_params(input: &SimpleLimitOrder) -> Result<MarketIncreaseOrderCalcOutput, Box<dyn std::error::Error>> {
+    println!("Starting limit order parameter calculations...");
+
+    Token::from_name(&input.collateral_token)
+        .ok_or("Unsupported token")?;
+    let index_info: TokenInfo = Token::from_name(&input.index_token)
+        .ok_or("Unsupported index token")?
+        .info();
+    let trigger_price: U256 = scale_usd_price_to_contract_price(&input.trigger_price, index_info.decimals)?;
+    println!("Scaled trigger price: {}", trigger_price);
+
+    // Position sizing is the same as a market increase, the collateral is valued at its current oracle price
+    let sizing: PositionSizing = calculate_position_sizing(&market_equivalent_of(input)).await?;
+
+    // Longs buy at the trigger so may pay up to trigger + slippage, shorts sell so accept down to trigger - slippage
+    let acceptable_price: U256 = apply_slippage(trigger_price, input.is_long, input.slippage_bps)?;
+
+    // Limit orders can rest for a long time before a keeper executes them, so the fee gets the larger cushion
+    let swap_path_length: usize = Markets::get_swap_path_for_collateral(&input.collateral_token).len();
+    let execution_fee: U256 = calculate_execution_fee(OrderType::LimitIncrease, swap_path_length, U256::zero(), RESTING_ORDER_FEE_MULTIPLIER_BPS).await?;
+
+    Ok(MarketIncreaseOrderCalcOutput {
+        is_long: input.is_long,
+        collateral_amount: sizing.collateral_amount,
+        size_delta_usd: sizing.size_delta_usd,
+        initial_collateral_delta_amount: U256::zero(),
+        trigger_price,
+        acceptable_price,
+        execution_fee,
+        min_output_amount: U256::zero(),
+    })
+}
+
+pub async fn get_order_object_from_simple_limit_order(input: &SimpleLimitOrder) -> Result<OrderObject, Box<dyn std::error::Error>> {
+    let market_equivalent: SimpleOrder = market_equivalent_of(input);
+
+    let number_params: MarketIncreaseOrderCalcOutput = calculate_limit_increase_order_params(input).await?;
+    let address_params: AddressesForMarketIncreaseOrder = get_addresses_for_market_increase_order(&market_equivalent)?;
+    let full_object: OrderObject = create_full_order_object(address_params, number_params, OrderType::LimitIncrease)?;
+
+    Ok(full_object)
+}
+
+// Position sizing is identical to a market increase, only the execution conditions differ
+fn market_equivalent_of(input: &SimpleLimitOrder) -> SimpleOrder {
+    SimpleOrder {
+        is_long: input.is_long,
+        index_token: input.index_token.clone(),
+        collateral_amount: input.collateral_amount.clone(),
+        collateral_token: input.collateral_token.clone(),
+        leverage_factor: input.leverage_factor,
+        slippage_bps: input.slippage_bps,
+    }
+}
@@ -1,26 +1,125 @@
 use crate::contract_caller::utils::local_signer::get_local_signer;
-use crate::contract_caller::utils::structs::{MarketDecreaseOrderCalcOutput, Token, TokenInfo, AddressesForMarketIncreaseOrder, OrderObject, Markets, SimpleClosePosition};
+use crate::contract_caller::utils::structs::{MarketDecreaseOrderCalcOutput, Token, AddressesForMarketDecreaseOrder, OrderObject, RawOrderObject, OrderType, DecreasePositionSwapType, Markets, Position, SimpleClosePosition};
+use crate::contract_caller::utils::reader_interface::reader_functions::{get_position_key, get_position};
 use ethers::signers::Signer;
-use ethers::types::U256;
+use ethers::types::{H160, H256, U256};
 use crate::contract_caller::order_builder::get_price::fetch_token_price;
-use crate::contract_caller::utils::gas_calculator::calculate_execution_fee;
+use crate::contract_caller::order_builder::calculate_acceptable_price::calculate_acceptable_price;
+use crate::contract_caller::utils::gas_calculator::{calculate_execution_fee, MARKET_ORDER_FEE_MULTIPLIER_BPS};
 
 pub async fn calculate_market_decrease_order_params(input: &SimpleClosePosition) -> Result<MarketDecreaseOrderCalcOutput, Box<dyn std::error::Error>> {
-    const USD_SCALE_FACTOR: u32 = 30;
+    println!("Starting close position calculations...");
 
     let trigger_price: U256 = U256::from(0);
     let min_output_amount: U256 = U256::from(0);
-    let estimated_gas: u64 = 3000000000000000;
-    let estimated_gas_u256: U256 = U256::from(estimated_gas);
-    let collateral_info: TokenInfo = Token::from_name(&input.collateral_token)
-        .ok_or("Unsupported token")?
-        .info();
+    let callback_gas_limit: U256 = U256::from(0);
+    let is_long: bool = input.is_long;
+
+    let position: Position = get_open_position(&input.index_token, &input.collateral_token, is_long).await?;
+
+    let size_delta_usd: U256 = position.size_in_usd;
+    let initial_collateral_delta_amount: U256 = position.collateral_amount;
 
-    let collateral_amount_raw: U256 = U256::from_dec_str(&input.collateral_amount)?;
-    let decimal_adjusted_value: U256 = collateral_amount_raw
-    .checked_div(U256::exp10(collateral_info.decimals as usize))
-    .ok_or("Conversion to USD value error")?;
     let price_output = fetch_token_price(input.index_token.clone()).await?;
-    let acceptable_price: U256 = U256::from_dec_str(&price_output.min_price_full)?;
-    
-}
\ No newline at end of file
+    let acceptable_price: U256 = calculate_acceptable_price(&price_output, is_long, false, input.slippage_bps)?;
+    let execution_fee: U256 = calculate_execution_fee(OrderType::MarketDecrease, 0, callback_gas_limit, MARKET_ORDER_FEE_MULTIPLIER_BPS).await?;
+
+    Ok(MarketDecreaseOrderCalcOutput {
+        is_long,
+        size_delta_usd,
+        initial_collateral_delta_amount,
+        trigger_price,
+        acceptable_price,
+        execution_fee,
+        callback_gas_limit,
+        min_output_amount,
+        should_unwrap_native_token: input.should_unwrap_native_token,
+    })
+}
+
+// Reads the signer's live position for the given market, collateral and direction
+pub async fn get_open_position(index_token: &str, collateral_token: &str, is_long: bool) -> Result<Position, Box<dyn std::error::Error>> {
+    let wallet = get_local_signer()?;
+    let account: H160 = wallet.address();
+    let market: H160 = Markets::get_market_address(index_token)
+        .ok_or("Market address not found for index token")?
+        .parse()
+        .map_err(|e| format!("Error parsing market address: {}", e))?;
+    let collateral_token: H160 = Token::token_address_from_name(collateral_token)
+        .ok_or("Unsupported token")?
+        .parse()
+        .map_err(|e| format!("Error parsing collateral token address: {}", e))?;
+
+    let position_key: H256 = get_position_key(account, market, collateral_token, is_long);
+    println!("Position key: {:?}", position_key);
+    let position: Position = get_position(position_key).await?
+        .filter(|position| !position.size_in_usd.is_zero())
+        .ok_or("No open position found for this market, collateral and direction")?;
+
+    Ok(position)
+}
+
+pub fn get_addresses_for_market_decrease_order(input: &SimpleClosePosition) -> Result<AddressesForMarketDecreaseOrder, Box<dyn std::error::Error>> {
+    let default_order: RawOrderObject = RawOrderObject::default();
+
+    // Pay out to the signer unless a different receiver was given
+    let receiver_str: String = if input.reciever.is_empty() {
+        let wallet = get_local_signer()?;
+        format!("{:?}", wallet.address())
+    } else {
+        input.reciever.clone()
+    };
+
+    let market: String = Markets::get_market_address(&input.index_token)
+        .ok_or("Market address not found for index token")?;
+    println!("Market address for {}: {}", input.index_token, market);
+
+    Ok(AddressesForMarketDecreaseOrder {
+        receiver: receiver_str,
+        callback_contract: default_order.callback_contract,
+        ui_fee_receiver: default_order.ui_fee_receiver,
+        market,
+        initial_collateral_token: input.collateral_token.to_string(),
+        swap_path: Vec::new(),
+        referral_code: default_order.referral_code,
+    })
+}
+
+pub fn create_full_decrease_order_object(
+    address_data: AddressesForMarketDecreaseOrder,
+    calc_output: MarketDecreaseOrderCalcOutput,
+    order_type: OrderType,
+) -> Result<OrderObject, Box<dyn std::error::Error>> {
+    let referral_code: String = address_data.referral_code;
+
+    OrderObject::try_from(RawOrderObject {
+        is_long: calc_output.is_long,
+        position_asset: address_data.initial_collateral_token.clone(),
+        amount: "0".to_string(),
+        receiver: address_data.receiver,
+        callback_contract: address_data.callback_contract,
+        ui_fee_receiver: address_data.ui_fee_receiver,
+        market: address_data.market,
+        initial_collateral_token: address_data.initial_collateral_token,
+        swap_path: address_data.swap_path,
+        size_delta_usd: calc_output.size_delta_usd.to_string(),
+        initial_collateral_delta_amount: calc_output.initial_collateral_delta_amount.to_string(),
+        trigger_price: calc_output.trigger_price.to_string(),
+        acceptable_price: calc_output.acceptable_price.to_string(),
+        execution_fee: calc_output.execution_fee.to_string(),
+        callback_gas_limit: calc_output.callback_gas_limit.to_string(),
+        min_output_amount: calc_output.min_output_amount.to_string(),
+        order_type,
+        decrease_position_swap_type: DecreasePositionSwapType::NoSwap,
+        should_unwrap_native_token: calc_output.should_unwrap_native_token,
+        referral_code,
+    })
+}
+
+pub async fn get_order_object_from_simple_close_position(input: &SimpleClosePosition) -> Result<OrderObject, Box<dyn std::error::Error>> {
+    let number_params: MarketDecreaseOrderCalcOutput = calculate_market_decrease_order_params(input).await?;
+    let address_params: AddressesForMarketDecreaseOrder = get_addresses_for_market_decrease_order(input)?;
+    let full_object: OrderObject = create_full_decrease_order_object(address_params, number_params, OrderType::MarketDecrease)?;
+
+    Ok(full_object)
+}
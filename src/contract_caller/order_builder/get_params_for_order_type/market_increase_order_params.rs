@@ -1,13 +1,13 @@
 use crate::contract_caller::utils::local_signer::get_local_signer;
-use crate::contract_caller::utils::structs::{MarketIncreaseOrderCalcOutput, Token, TokenInfo, AddressesForMarketIncreaseOrder, OrderObject, Markets, SimpleOrder};
+use crate::contract_caller::utils::structs::{MarketIncreaseOrderCalcOutput, PositionSizing, Token, AddressesForMarketIncreaseOrder, OrderObject, RawOrderObject, OrderType, DecreasePositionSwapType, Markets, SimpleOrder};
 use ethers::signers::Signer;
 use ethers::types::U256;
 use crate::contract_caller::order_builder::get_price::fetch_token_price;
-use crate::contract_caller::utils::gas_calculator::calculate_execution_fee;
+use crate::contract_caller::order_builder::calculate_acceptable_price::calculate_acceptable_price;
+use crate::contract_caller::order_builder::calculate_leverage::calculate_position_sizing;
+use crate::contract_caller::utils::gas_calculator::{calculate_execution_fee, MARKET_ORDER_FEE_MULTIPLIER_BPS};
 
 pub async fn calculate_market_increase_order_params(input: &SimpleOrder) -> Result<MarketIncreaseOrderCalcOutput, Box<dyn std::error::Error>> {
-    const USD_SCALE_FACTOR: u32 = 30; // Scaling factor for USD values
-
     println!("Starting order parameter calculations...");
 
     if input.leverage_factor > 50.0 {
@@ -17,28 +17,19 @@ pub async fn calculate_market_increase_order_params(input: &SimpleOrder) -> Resu
     let initial_collateral_delta_amount: U256 = U256::from(0);
     let trigger_price: U256 = U256::from(0);
     let min_output_amount: U256 = U256::from(0);
-    let estimated_gas: u64 = 5000000000000000;
-    let estimated_gas_u256: U256 = U256::from(estimated_gas);
     let is_long: bool = input.is_long;
-    let collateral_info: TokenInfo = Token::from_name(&input.collateral_token)
-        .ok_or("Unsupported token")?
-        .info();
+    Token::from_name(&input.collateral_token)
+        .ok_or("Unsupported token")?;
 
-    let collateral_amount_raw: U256 = U256::from_dec_str(&input.collateral_amount)?;
+    // Value the collateral at its oracle price and apply leverage, keeping full precision throughout
+    let sizing: PositionSizing = calculate_position_sizing(input).await?;
+    let collateral_amount_raw: U256 = sizing.collateral_amount;
+    let size_delta_usd: U256 = sizing.size_delta_usd;
 
-    
-    // Calculate the USD value of the collateral
-    let actual_usd_value: U256 = collateral_amount_raw
-    .checked_div(U256::exp10(collateral_info.decimals as usize))
-    .ok_or("Conversion to USD value error")?;
     let price_output = fetch_token_price(input.index_token.clone()).await?;
-    let acceptable_price: U256 = U256::from_dec_str(&price_output.min_price_full)?;
-    let leverage_as_u256: U256 = U256::from(input.leverage_factor as u64);
-    let leveraged_usd_value: U256 = actual_usd_value.checked_mul(leverage_as_u256)
-        .ok_or("Leverage application error")?;
-    let size_delta_usd: U256 = leveraged_usd_value.checked_mul(U256::exp10(USD_SCALE_FACTOR as usize))
-        .ok_or("Final USD scaling error")?;
-    let execution_fee: U256 = calculate_execution_fee(estimated_gas).await?;
+    let acceptable_price: U256 = calculate_acceptable_price(&price_output, is_long, true, input.slippage_bps)?;
+    let swap_path_length: usize = Markets::get_swap_path_for_collateral(&input.collateral_token).len();
+    let execution_fee: U256 = calculate_execution_fee(OrderType::MarketIncrease, swap_path_length, U256::zero(), MARKET_ORDER_FEE_MULTIPLIER_BPS).await?;
 
     Ok(MarketIncreaseOrderCalcOutput {
         is_long,
@@ -47,7 +38,7 @@ pub async fn calculate_market_increase_order_params(input: &SimpleOrder) -> Resu
         initial_collateral_delta_amount,
         trigger_price,
         acceptable_price,
-        execution_fee: estimated_gas_u256,
+        execution_fee,
         min_output_amount
     })
 }
@@ -59,7 +50,7 @@ pub fn get_addresses_for_market_increase_order(input: &SimpleOrder) -> Result<Ad
 
 
     println!("Full Address: {}", receiver_str);
-    let default_order: OrderObject = OrderObject::default();
+    let default_order: RawOrderObject = RawOrderObject::default();
     let mut market: String = String::new();
     let swap_path: Vec<String> = Markets::get_swap_path_for_collateral(&input.collateral_token);
 
@@ -85,11 +76,12 @@ pub fn get_addresses_for_market_increase_order(input: &SimpleOrder) -> Result<Ad
 pub fn create_full_order_object(
     address_data: AddressesForMarketIncreaseOrder,
     calc_output: MarketIncreaseOrderCalcOutput,
+    order_type: OrderType,
 ) -> Result<OrderObject, Box<dyn std::error::Error>> {
     let referral_code: String = address_data.referral_code;
     println!("TESTING: COLLATERAL AMOUNT = {}", calc_output.collateral_amount.to_string());
 
-    Ok(OrderObject {
+    OrderObject::try_from(RawOrderObject {
         is_long: calc_output.is_long,
         position_asset: address_data.initial_collateral_token.clone(),
         amount: calc_output.collateral_amount.to_string(),
@@ -106,8 +98,8 @@ pub fn create_full_order_object(
         execution_fee: calc_output.execution_fee.to_string(),
         callback_gas_limit: "0".to_string(), 
         min_output_amount: calc_output.min_output_amount.to_string(),
-        order_type: 2,
-        decrease_position_swap_type: 0, 
+        order_type,
+        decrease_position_swap_type: DecreasePositionSwapType::NoSwap, 
         should_unwrap_native_token: false,
         referral_code,
     })
@@ -116,7 +108,7 @@ pub fn create_full_order_object(
 pub async fn get_order_object_from_simple_order(input: &SimpleOrder) -> Result<OrderObject, Box<dyn std::error::Error>> {
     let number_params: MarketIncreaseOrderCalcOutput = calculate_market_increase_order_params(input).await?;
     let address_params: AddressesForMarketIncreaseOrder = get_addresses_for_market_increase_order(input)?;
-    let full_object: OrderObject = create_full_order_object(address_params, number_params)?;
+    let full_object: OrderObject = create_full_order_object(address_params, number_params, OrderType::MarketIncrease)?;
 
     Ok(full_object)
 }
\ No newline at end of file
@@ -1 +1,5 @@
-pub mod market_increase_order_params;
\ No newline at end of file
+pub mod market_increase_order_params;
+pub mod market_decrease_order_params;
+pub mod limit_increase_order_params;
+pub mod trigger_decrease_order_params;
+pub mod swap_order_params;
\ No newline at end of file
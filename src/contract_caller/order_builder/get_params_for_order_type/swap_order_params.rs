@@ -0,0 +1,120 @@
+use crate::contract_caller::utils::local_signer::get_local_signer;
+use crate::contract_caller::utils::structs::{SwapOrderCalcOutput, Token, AddressesForSwapOrder, OrderObject, RawOrderObject, OrderType, DecreasePositionSwapType, Markets, SimpleSwap};
+use ethers::signers::Signer;
+use ethers::types::U256;
+use crate::contract_caller::order_builder::get_price::fetch_token_price;
+use crate::contract_caller::utils::constants::BPS_DIVISOR;
+use crate::contract_caller::utils::gas_calculator::{calculate_execution_fee, MARKET_ORDER_FEE_MULTIPLIER_BPS, RESTING_ORDER_FEE_MULTIPLIER_BPS};
+
+pub async fn calculate_swap_order_params(input: &SimpleSwap) -> Result<SwapOrderCalcOutput, Box<dyn std::error::Error>> {
+    println!("Starting swap parameter calculations...");
+
+    if input.slippage_bps >= BPS_DIVISOR {
+        return Err("Slippage must be below 10000 bps".into());
+    }
+    Token::from_name(&input.from_token).ok_or("Unsupported from token")?;
+    Token::from_name(&input.to_token).ok_or("Unsupported to token")?;
+
+    let amount: U256 = U256::from_dec_str(&input.amount)
+        .map_err(|e| format!("Error parsing swap amount to U256: {}", e))?;
+
+    let (order_type, min_output_amount, fee_multiplier_bps): (OrderType, U256, u32) = match &input.limit_output_amount {
+        // Limit swaps execute once the pools can pay out at least the requested amount
+        Some(limit_output_amount) => {
+            let min_output_amount: U256 = U256::from_dec_str(limit_output_amount)
+                .map_err(|e| format!("Error parsing limit_output_amount to U256: {}", e))?;
+            (OrderType::LimitSwap, min_output_amount, RESTING_ORDER_FEE_MULTIPLIER_BPS)
+        },
+        None => {
+            // Quote with the prices least favourable to us. Both oracle prices are USD per token unit,
+            // so amount * from price is a 30 decimal USD value and dividing by the to price gives to token units.
+            let from_price = fetch_token_price(input.from_token.clone()).await?;
+            let to_price = fetch_token_price(input.to_token.clone()).await?;
+            let from_min_price: U256 = U256::from_dec_str(&from_price.min_price_full)?;
+            let to_max_price: U256 = U256::from_dec_str(&to_price.max_price_full)?;
+
+            let quoted_output_amount: U256 = amount
+                .checked_mul(from_min_price)
+                .ok_or("Swap quote overflow")?
+                .checked_div(to_max_price)
+                .ok_or("Swap quote division error")?;
+            println!("Quoted output amount: {}", quoted_output_amount);
+
+            let min_output_amount: U256 = quoted_output_amount
+                .checked_mul(U256::from(BPS_DIVISOR - input.slippage_bps))
+                .ok_or("Slippage application error")?
+                / U256::from(BPS_DIVISOR);
+            (OrderType::MarketSwap, min_output_amount, MARKET_ORDER_FEE_MULTIPLIER_BPS)
+        },
+    };
+    println!("Min output amount: {}", min_output_amount);
+
+    let swap_path: Vec<String> = Markets::get_swap_path(&input.from_token, &input.to_token)
+        .ok_or("No swap path found between these tokens")?;
+    let execution_fee: U256 = calculate_execution_fee(order_type, swap_path.len(), U256::zero(), fee_multiplier_bps).await?;
+
+    Ok(SwapOrderCalcOutput {
+        amount,
+        min_output_amount,
+        execution_fee,
+        order_type,
+    })
+}
+
+pub fn get_addresses_for_swap_order(input: &SimpleSwap) -> Result<AddressesForSwapOrder, Box<dyn std::error::Error>> {
+    let wallet = get_local_signer()?;
+    let receiver_str: String = format!("{:?}", wallet.address());
+    let default_order: RawOrderObject = RawOrderObject::default();
+
+    let swap_path: Vec<String> = Markets::get_swap_path(&input.from_token, &input.to_token)
+        .ok_or("No swap path found between these tokens")?;
+    println!("Swap path for {} -> {}: {:?}", input.from_token, input.to_token, swap_path);
+
+    Ok(AddressesForSwapOrder {
+        receiver: receiver_str,
+        callback_contract: default_order.callback_contract,
+        ui_fee_receiver: default_order.ui_fee_receiver,
+        initial_collateral_token: input.from_token.to_string(),
+        swap_path,
+        referral_code: default_order.referral_code,
+    })
+}
+
+pub fn create_full_swap_order_object(
+    address_data: AddressesForSwapOrder,
+    calc_output: SwapOrderCalcOutput,
+) -> Result<OrderObject, Box<dyn std::error::Error>> {
+    let default_order: RawOrderObject = RawOrderObject::default();
+
+    // Swaps have no position, so the market is left as the zero address
+    OrderObject::try_from(RawOrderObject {
+        is_long: false,
+        position_asset: address_data.initial_collateral_token.clone(),
+        amount: calc_output.amount.to_string(),
+        receiver: address_data.receiver,
+        callback_contract: address_data.callback_contract,
+        ui_fee_receiver: address_data.ui_fee_receiver,
+        market: default_order.market,
+        initial_collateral_token: address_data.initial_collateral_token,
+        swap_path: address_data.swap_path,
+        size_delta_usd: "0".to_string(),
+        initial_collateral_delta_amount: calc_output.amount.to_string(),
+        trigger_price: "0".to_string(),
+        acceptable_price: "0".to_string(),
+        execution_fee: calc_output.execution_fee.to_string(),
+        callback_gas_limit: "0".to_string(),
+        min_output_amount: calc_output.min_output_amount.to_string(),
+        order_type: calc_output.order_type,
+        decrease_position_swap_type: DecreasePositionSwapType::NoSwap,
+        should_unwrap_native_token: false,
+        referral_code: address_data.referral_code,
+    })
+}
+
+pub async fn get_order_object_from_simple_swap(input: &SimpleSwap) -> Result<OrderObject, Box<dyn std::error::Error>> {
+    let number_params: SwapOrderCalcOutput = calculate_swap_order_params(input).await?;
+    let address_params: AddressesForSwapOrder = get_addresses_for_swap_order(input)?;
+    let full_object: OrderObject = create_full_swap_order_object(address_params, number_params)?;
+
+    Ok(full_object)
+}
@@ -0,0 +1,92 @@
+use crate::contract_caller::utils::structs::{MarketDecreaseOrderCalcOutput, Token, TokenInfo, AddressesForMarketDecreaseOrder, OrderObject, OrderType, Position, SimpleClosePosition, SimpleTriggerDecrease};
+use crate::contract_caller::order_builder::get_params_for_order_type::market_decrease_order_params::{get_open_position, get_addresses_for_market_decrease_order, create_full_decrease_order_object};
+use crate::contract_caller::order_builder::get_price::scale_usd_price_to_contract_price;
+use crate::contract_caller::order_builder::calculate_acceptable_price::apply_slippage;
+use crate::contract_caller::utils::gas_calculator::{calculate_execution_fee, RESTING_ORDER_FEE_MULTIPLIER_BPS};
+use ethers::types::U256;
+
+pub async fn calculate_trigger_decrease_order_params(input: &SimpleTriggerDecrease, order_type: OrderType) -> Result<MarketDecreaseOrderCalcOutput, Box<dyn std::error::Error>> {
+    println!("Starting trigger order parameter calculations...");
+
+    let is_stop_loss: bool = match order_type {
+        OrderType::LimitDecrease => false,
+        OrderType::StopLossDecrease => true,
+        _ => return Err("Trigger decrease orders must be LimitDecrease or StopLossDecrease".into()),
+    };
+
+    let min_output_amount: U256 = U256::from(0);
+    let callback_gas_limit: U256 = U256::from(0);
+    let is_long: bool = input.is_long;
+
+    let index_info: TokenInfo = Token::from_name(&input.index_token)
+        .ok_or("Unsupported index token")?
+        .info();
+    let trigger_price: U256 = scale_usd_price_to_contract_price(&input.trigger_price, index_info.decimals)?;
+    println!("Scaled trigger price: {}", trigger_price);
+
+    let position: Position = get_open_position(&input.index_token, &input.collateral_token, is_long).await?;
+
+    // No explicit size closes the whole position
+    let size_delta_usd: U256 = match &input.size_delta_usd {
+        Some(size) => U256::from_dec_str(size)
+            .map_err(|e| format!("Error parsing size_delta_usd to U256: {}", e))?,
+        None => position.size_in_usd,
+    };
+    if size_delta_usd.is_zero() || size_delta_usd > position.size_in_usd {
+        return Err("Size delta must be greater than zero and no larger than the open position".into());
+    }
+
+    let initial_collateral_delta_amount: U256 = U256::from_dec_str(&input.collateral_withdrawal_amount)
+        .map_err(|e| format!("Error parsing collateral_withdrawal_amount to U256: {}", e))?;
+    if initial_collateral_delta_amount > position.collateral_amount {
+        return Err("Collateral withdrawal exceeds the position's collateral".into());
+    }
+
+    // Closing a long sells the index token and closing a short buys it back, so slippage is applied
+    // below the trigger for longs and above it for shorts. Stop losses fire once price has already
+    // moved through the trigger, so without any slippage allowance they would rarely fill.
+    if is_stop_loss && input.slippage_bps == 0 {
+        println!("Warning: stop loss with zero slippage may not fill once triggered");
+    }
+    let acceptable_price: U256 = apply_slippage(trigger_price, !is_long, input.slippage_bps)?;
+
+    // Trigger orders rest until the price is hit, so the fee gets the larger cushion
+    let execution_fee: U256 = calculate_execution_fee(order_type, 0, callback_gas_limit, RESTING_ORDER_FEE_MULTIPLIER_BPS).await?;
+
+    Ok(MarketDecreaseOrderCalcOutput {
+        is_long,
+        size_delta_usd,
+        initial_collateral_delta_amount,
+        trigger_price,
+        acceptable_price,
+        execution_fee,
+        callback_gas_limit,
+        min_output_amount,
+        should_unwrap_native_token: input.should_unwrap_native_token,
+    })
+}
+
+pub async fn get_order_object_from_take_profit(input: &SimpleTriggerDecrease) -> Result<OrderObject, Box<dyn std::error::Error>> {
+    get_trigger_decrease_order_object(input, OrderType::LimitDecrease).await
+}
+
+pub async fn get_order_object_from_stop_loss(input: &SimpleTriggerDecrease) -> Result<OrderObject, Box<dyn std::error::Error>> {
+    get_trigger_decrease_order_object(input, OrderType::StopLossDecrease).await
+}
+
+async fn get_trigger_decrease_order_object(input: &SimpleTriggerDecrease, order_type: OrderType) -> Result<OrderObject, Box<dyn std::error::Error>> {
+    let close_equivalent: SimpleClosePosition = SimpleClosePosition {
+        is_long: input.is_long,
+        reciever: String::new(),
+        index_token: input.index_token.clone(),
+        collateral_token: input.collateral_token.clone(),
+        slippage_bps: input.slippage_bps,
+        should_unwrap_native_token: input.should_unwrap_native_token,
+    };
+
+    let number_params: MarketDecreaseOrderCalcOutput = calculate_trigger_decrease_order_params(input, order_type).await?;
+    let address_params: AddressesForMarketDecreaseOrder = get_addresses_for_market_decrease_order(&close_equivalent)?;
+    let full_object: OrderObject = create_full_decrease_order_object(address_params, number_params, order_type)?;
+
+    Ok(full_object)
+}
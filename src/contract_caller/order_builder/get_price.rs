@@ -1,35 +1,75 @@
+use crate::contract_caller::utils::constants::FLOAT_PRECISION;
 use crate::contract_caller::utils::structs::{TokenPriceFromApiResponse, ApiResponse};
+use ethers::types::{Address, U256};
 use reqwest;
+use std::collections::HashMap;
 
 pub async fn fetch_token_price(mut index_token: String) -> Result<TokenPriceFromApiResponse, Box<dyn std::error::Error>> {
-    let url: &str = "https://arbitrum-api.gmxinfra.io/signed_prices/latest";
-    
-    // Get the raw response
-    let response = reqwest::get(url).await?;
-    let response_text = response.text().await?;
     if index_token == "WBTC" {
         index_token = "WBTC.b".to_string();
     }
-
-    // Deserialize the response text to ApiResponse
-    let response_json: ApiResponse = serde_json::from_str(&response_text)?;
+    if index_token == "USDCE" {
+        index_token = "USDC.e".to_string();
+    }
 
     println!("Searching for token: {}", index_token);
 
     // Find the relevant price data for the specified token
+    let token_price: TokenPriceFromApiResponse = fetch_token_prices_by_address().await?
+        .into_values()
+        .find(|price_data| price_data.token_symbol == index_token)
+        .ok_or("Token not found in price data")?;
+
+    Ok(token_price)
+}
+
+// Every signed price in one request, keyed by token address so Reader market structs can be priced directly
+pub async fn fetch_token_prices_by_address() -> Result<HashMap<Address, TokenPriceFromApiResponse>, Box<dyn std::error::Error>> {
+    let url: &str = "https://arbitrum-api.gmxinfra.io/signed_prices/latest";
+
+    let response = reqwest::get(url).await?;
+    let response_text = response.text().await?;
+    let response_json: ApiResponse = serde_json::from_str(&response_text)?;
+
+    let mut prices: HashMap<Address, TokenPriceFromApiResponse> = HashMap::new();
     for price_data in response_json.signed_prices {
-        if price_data.token_symbol == index_token {
-            let min_price = price_data.min_price_full.unwrap_or_default();
-            let max_price = price_data.max_price_full.unwrap_or_default();
-
-            return Ok(TokenPriceFromApiResponse {
-                token_symbol: price_data.token_symbol,
-                min_price_full: min_price,
-                max_price_full: max_price,
-            });
-        }
+        let token_address: Address = match price_data.token_address.parse() {
+            Ok(token_address) => token_address,
+            Err(_) => continue,
+        };
+        prices.insert(token_address, TokenPriceFromApiResponse {
+            token_symbol: price_data.token_symbol,
+            min_price_full: price_data.min_price_full.unwrap_or_default(),
+            max_price_full: price_data.max_price_full.unwrap_or_default(),
+        });
     }
 
-    Err("Token not found in price data".into())
+    Ok(prices)
 }
 
+// GMX stores prices per unit of token with 30 decimals of USD precision, so a token with
+// `token_decimals` decimals is priced as usd_price * 10^(30 - token_decimals)
+pub fn scale_usd_price_to_contract_price(usd_price: &str, token_decimals: u8) -> Result<U256, Box<dyn std::error::Error>> {
+    let price_decimals: usize = FLOAT_PRECISION
+        .checked_sub(token_decimals as usize)
+        .ok_or("Token decimals exceed USD precision")?;
+
+    let (integer_part, fractional_part) = match usd_price.trim().split_once('.') {
+        Some((integer_part, fractional_part)) => (integer_part, fractional_part),
+        None => (usd_price.trim(), ""),
+    };
+
+    // Digits beyond the contract precision cannot be represented and are dropped
+    let fractional_part: &str = &fractional_part[..fractional_part.len().min(price_decimals)];
+    let padded_fraction: String = format!("{:0<width$}", fractional_part, width = price_decimals);
+    let integer_part: &str = if integer_part.is_empty() { "0" } else { integer_part };
+
+    let scaled_price: U256 = U256::from_dec_str(&format!("{}{}", integer_part, padded_fraction))
+        .map_err(|e| format!("Error parsing price {}: {}", usd_price, e))?;
+
+    if scaled_price.is_zero() {
+        return Err("Price must be greater than zero".into());
+    }
+
+    Ok(scaled_price)
+}
@@ -1,3 +1,5 @@
 pub mod get_params_for_order_type;
 pub mod calculate_leverage;
+pub mod calculate_acceptable_price;
+pub mod calculate_liquidation_price;
 pub mod get_price;
\ No newline at end of file
@@ -1,108 +1,91 @@
 use ethers::prelude::*;
+use ethers::abi::{AbiEncode, Token as AbiToken};
 use ethers::types::transaction::eip2718::TypedTransaction;
-use ethers::types::{H160, Address, U256, TransactionRequest, NameOrAddress};
+use ethers::types::{H160, H256, Address, U256, NameOrAddress};
+use ethers::utils::format_ether;
 use dotenv::dotenv;
-use anyhow::anyhow;
-use std::str::FromStr;
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use super::utils::local_signer::get_local_signer;
-use super::utils::structs::{OrderObject, Token};
-use super::utils::contract_addresses::{Contracts, CreateOrderParams, CreateOrderParamsAddresses, CreateOrderParamsNumbers};
+use super::utils::structs::{
+    ApprovalRequest, BundledCall, GmxError, KeyHashes, OrderObject, OrderSubmission, OrderTransactionBundle, OrderType,
+    PreflightError, Token, TokenPriceFromApiResponse, TransactionSettings,
+};
+use super::utils::approval_manager::{build_router_approval, send_approval_if_needed, ApprovalAmount};
+use super::utils::contract_addresses::{
+    Contracts, CreateOrderCall, CreateOrderParams, CreateOrderParamsAddresses, CreateOrderParamsNumbers, MulticallCall,
+    PriceProps, SendTokensCall, SendWntCall, SimulateExecuteOrderCall, SimulatePricesParams,
+    ARBITRUM_CHAIN_ID, EXCHANGE_ROUTER_ADDRESS, ORDER_VAULT_ADDRESS,
+};
+use super::utils::hash_utils::hash_data;
 
 use crate::contract_caller::connect_provider::connect_provider;
-use crate::contract_caller::utils::gas_calculator::get_current_gas_price;
+use crate::contract_caller::utils::order_events::get_order_key_from_receipt;
+use crate::contract_caller::order_builder::get_price::fetch_token_prices_by_address;
+use crate::contract_caller::utils::transaction_sender::{send_typed_transaction, to_preflight_error, SignerClient};
 
 
-pub async fn sol_call(order_object: OrderObject) -> Result<(), Box<dyn std::error::Error>> {
-    dotenv().ok();
+// Submits the order and returns the submission details. The order key can be passed to watch_order, update_order or cancel_order.
+pub async fn sol_call(order_object: OrderObject) -> Result<OrderSubmission, Box<dyn std::error::Error>> {
+    sol_call_with_settings(order_object, ApprovalAmount::Exact, &TransactionSettings::default()).await
+}
 
-    // ---------------------------------------------------------
-    //                      Initialisation
-    // ---------------------------------------------------------
+pub async fn sol_call_with_settings(
+    order_object: OrderObject,
+    approval_amount: ApprovalAmount,
+    settings: &TransactionSettings,
+) -> Result<OrderSubmission, Box<dyn std::error::Error>> {
+    dotenv().ok();
 
     let wallet = get_local_signer()?;
-    let provider: Provider<Http> = connect_provider().await?;
-    let arc_provider: Arc<Provider<Http>> = Arc::new(provider);
-    let contracts: Contracts = Contracts::new(arc_provider.clone());
-    let client: SignerMiddleware<Arc<Provider<Http>>, _> = SignerMiddleware::new(arc_provider.clone(), wallet.clone());
-
-
-
-    // Parse number values to U256
-    let amount_u256: U256 = U256::from_dec_str(&order_object.amount)
-    .map_err(|e| format!("Error parsing amount to U256: {}", e))?;
-
-    let size_delta_usd: U256 = U256::from_dec_str(&order_object.size_delta_usd)
-        .map_err(|e| format!("Error parsing size_delta_usd to U256: {}", e))?;
-
-    let initial_collateral_delta_amount: U256 = U256::from_dec_str(&order_object.initial_collateral_delta_amount)
-        .map_err(|e| format!("Error parsing initial_collateral_delta_amount to U256: {}", e))?;
-
-    let trigger_price: U256 = U256::from_dec_str(&order_object.trigger_price)
-        .map_err(|e| format!("Error parsing trigger_price to U256: {}", e))?;
-
-    let acceptable_price: U256 = U256::from_dec_str(&order_object.acceptable_price)
-        .map_err(|e| format!("Error parsing acceptable_price to U256: {}", e))?;
-
-    let execution_fee: U256 = U256::from_dec_str(&order_object.execution_fee)
-        .map_err(|e| format!("Error parsing execution_fee to U256: {}", e))?;
+    let bundle: OrderTransactionBundle = build_order_transactions(&order_object, wallet.address(), approval_amount)?;
 
-    let callback_gas_limit: U256 = U256::from_dec_str(&order_object.callback_gas_limit)
-        .map_err(|e| format!("Error parsing callback_gas_limit to U256: {}", e))?;
+    send_order_transactions(bundle, settings).await
+}
 
-    let min_output_amount: U256 = U256::from_dec_str(&order_object.min_output_amount)
-        .map_err(|e| format!("Error parsing min_output_amount to U256: {}", e))?;
+// Build phase: encodes the approval and multicall for an order without a signer or RPC connection,
+// so the exact bytes can be reviewed or asserted on before anything is sent
+pub fn build_order_transactions(
+    order_object: &OrderObject,
+    sender: Address,
+    approval_amount: ApprovalAmount,
+) -> Result<OrderTransactionBundle, Box<dyn std::error::Error>> {
+    let exchange_router_address: H160 = EXCHANGE_ROUTER_ADDRESS.parse().expect("Invalid H160 address");
+    let order_vault_address: H160 = ORDER_VAULT_ADDRESS.parse().expect("Invalid H160 address");
 
-
-    // Parse addresses with error handling
-    let receiver = order_object.receiver.parse()
-        .map_err(|e| format!("Error parsing receiver address: {}", e))?;
-    let callback_contract = order_object.callback_contract.parse()
-        .map_err(|e| format!("Error parsing callback_contract address: {}", e))?;
-    let ui_fee_receiver = order_object.ui_fee_receiver.parse()
-        .map_err(|e| format!("Error parsing ui_fee_receiver address: {}", e))?;
-    let market = order_object.market.parse()
-        .map_err(|e| format!("Error parsing market address: {}", e))?;
-    let initial_collateral_token_str: String = Token::token_address_from_name(&order_object.initial_collateral_token)
-    .ok_or("Unsupported collateral token")?;
-    let initial_collateral_token: Address = initial_collateral_token_str.parse()
-        .map_err(|e| format!("Error parsing initial_collateral_token address: {}", e))?;
-
-
-    // Handle swap path parsing
-    let swap_path = order_object.swap_path.iter().map(|s| s.parse())
-        .collect::<Result<Vec<_>, _>>()
-        .map_err(|e| format!("Error parsing swap path addresses: {}", e))?;
-
-    // Handle referral code conversion
-    let referral_code_h256 = H256::from_str(&order_object.referral_code)
-        .map_err(|e| format!("Error converting referral code to H256: {}", e))?;
-    let referral_code_bytes = referral_code_h256.into();
+    // The order is already typed and validated by OrderObjectBuilder, so it maps straight onto the contract params
+    let amount_u256: U256 = order_object.amount;
+    let execution_fee: U256 = order_object.execution_fee;
+    let initial_collateral_token: Address = order_object.initial_collateral_token;
+    let collateral_token: Token = Token::from_address(initial_collateral_token)
+        .ok_or("Unsupported collateral token")?;
+    let collateral_token_name: &str = collateral_token.info().name;
+    let referral_code_bytes: [u8; 32] = order_object.referral_code.to_fixed_bytes();
 
 
 
     // Create the order object to be submitted to the chain
     let create_order_object: CreateOrderParams = CreateOrderParams {
         addresses: CreateOrderParamsAddresses {
-            receiver,
-            callback_contract,
-            ui_fee_receiver,
-            market,
+            receiver: order_object.receiver,
+            callback_contract: order_object.callback_contract,
+            ui_fee_receiver: order_object.ui_fee_receiver,
+            market: order_object.market,
             initial_collateral_token,
-            swap_path,
+            swap_path: order_object.swap_path.clone(),
         },
         numbers: CreateOrderParamsNumbers {
-            size_delta_usd,
-            initial_collateral_delta_amount,
-            trigger_price,
-            acceptable_price,
+            size_delta_usd: order_object.size_delta_usd,
+            initial_collateral_delta_amount: order_object.initial_collateral_delta_amount,
+            trigger_price: order_object.trigger_price,
+            acceptable_price: order_object.acceptable_price,
             execution_fee,
-            callback_gas_limit,
-            min_output_amount,
+            callback_gas_limit: order_object.callback_gas_limit,
+            min_output_amount: order_object.min_output_amount,
         },
-        order_type: order_object.order_type,
-        decrease_position_swap_type: order_object.decrease_position_swap_type,
+        order_type: order_object.order_type.into(),
+        decrease_position_swap_type: order_object.decrease_position_swap_type.into(),
         is_long: order_object.is_long,
         should_unwrap_native_token: order_object.should_unwrap_native_token,
         referral_code: referral_code_bytes,
@@ -116,190 +99,326 @@ pub async fn sol_call(order_object: OrderObject) -> Result<(), Box<dyn std::erro
     // ---------------------------------------------------------
 
     // ----------------------------------
-    //            Tx0: Approvals
+    //         Collateral Routing
     // ----------------------------------
 
-    let exchange_router_address: H160 = contracts.exchange_router_contract.address();
-    let order_vault_contract_address: H160 = contracts.order_vault_contract.address();
-    let router_contract_str: &str = "0x7452c558d45f8afc8c83dae62c3f8a5be19c71f6";
-    let router_contract: H160 = router_contract_str.parse().expect("Invalid H160 address");
-    let mut approval_contract: H160;
-    let double_check: Option<String> = Token::token_address_from_name(&order_object.initial_collateral_token);
-    println!("TESTING: TOKEN ADDRESS {:?}", double_check);
-    let tx0_builder = match order_object.initial_collateral_token.as_str() {
-        "USDC" => {
-            approval_contract = contracts.usdc_contract.address();
-            contracts.usdc_contract.approve(exchange_router_address, amount_u256)
-        },
-        "DAI" => {
-            approval_contract = contracts.dai_contract.address();
-            contracts.dai_contract.approve(exchange_router_address, amount_u256)
-        },
-        "ETH" => {
-            approval_contract = contracts.weth_contract.address();
-            contracts.weth_contract.approve(router_contract, amount_u256)
-        },
-        "WBTC" => {
-            approval_contract = contracts.wbtc_contract.address();
-            contracts.wbtc_contract.approve(exchange_router_address, amount_u256)
-        },
-        "LINK" => {
-            approval_contract = contracts.link_contract.address();
-            contracts.link_contract.approve(exchange_router_address, amount_u256)
-        },
-        "ARB" => {
-            approval_contract = contracts.arb_contract.address();
-            contracts.arb_contract.approve(exchange_router_address, amount_u256)
-        },
-        "UNI" => {
-            approval_contract = contracts.uni_contract.address();
-            contracts.uni_contract.approve(exchange_router_address, amount_u256)
-        },
-        "SOL" => {
-            approval_contract = contracts.sol_contract.address();
-            contracts.sol_contract.approve(exchange_router_address, amount_u256)
-        },
-        "USDT" => {
-            approval_contract = contracts.usdt_contract.address();
-            contracts.usdt_contract.approve(exchange_router_address, amount_u256)
-        },
-        "USDCE" => {
-            approval_contract = contracts.usdce_contract.address();
-            contracts.usdce_contract.approve(exchange_router_address, amount_u256)
-        },
-        _ => return Err("Unsupported collateral token".into()),
-    };
+    // Decrease orders are paid out of an open position, so there is nothing to approve or deposit
+    let requires_collateral_transfer: bool = !order_object.order_type.is_decrease();
 
-    let tx0_bytes: Bytes = tx0_builder.calldata()
-    .ok_or_else(|| anyhow!("Failed to build tx0 calldata"))?;
+    // ETH collateral is sent natively through sendWnt, which wraps it in the vault, so no WETH is needed up front
+    let is_native_collateral: bool = requires_collateral_transfer && collateral_token_name == "ETH";
+    let requires_token_transfer: bool = requires_collateral_transfer && !is_native_collateral;
 
-    println!("tx0 ok");
+    // ----------------------------------
+    //            Tx0: Approval
+    // ----------------------------------
+
+    let approval: Option<ApprovalRequest> = if requires_token_transfer {
+        Some(build_router_approval(collateral_token, sender, amount_u256, approval_amount)?)
+    } else {
+        None
+    };
 
     // ----------------------------------
     //            Tx1: Send Gas
     // ----------------------------------
 
-    let weth_amount: U256 = execution_fee;
-    println!("EXECUTION FEE = {}", weth_amount);
+    // Native collateral rides along with the execution fee, the vault splits them when the order is created
+    let weth_amount: U256 = if is_native_collateral {
+        execution_fee.checked_add(amount_u256).ok_or("Native collateral amount overflow")?
+    } else {
+        execution_fee
+    };
 
-    // Encode the sendWnt transaction calldata
-    let tx1_builder = contracts.exchange_router_contract.send_wnt(order_vault_contract_address, weth_amount);
-    let tx1_bytes: Bytes = tx1_builder.calldata()
-    .ok_or_else(|| anyhow!("Failed to build tx0 calldata"))?;
+    let mut calls: Vec<BundledCall> = vec!(BundledCall::SendWnt(SendWntCall { receiver: order_vault_address, amount: weth_amount }));
 
-    println!("tx1 ok");
-    
     // ----------------------------------
     //         Tx2: Vault Deposit
     // ----------------------------------
 
-    let tx2_builder = contracts.exchange_router_contract.send_tokens(initial_collateral_token, order_vault_contract_address, amount_u256);
-    println!("TEST: AMOUNT_U256 = {}", amount_u256);
-    let tx2_bytes: Bytes = tx2_builder.calldata()
-    .ok_or_else(|| anyhow!("Failed to build tx0 calldata"))?;
-
-    println!("tx2 ok");
+    if requires_token_transfer {
+        calls.push(BundledCall::SendTokens(SendTokensCall { token: initial_collateral_token, receiver: order_vault_address, amount: amount_u256 }));
+    }
 
     // ----------------------------------
     //         Tx3: Create Order
     // ----------------------------------
 
-    let tx3_builder = contracts.exchange_router_contract.create_order(create_order_object);
-    let tx3_bytes: Bytes = tx3_builder.calldata()
-    .ok_or_else(|| anyhow!("Failed to build tx0 calldata"))?;
+    calls.push(BundledCall::CreateOrder(Box::new(CreateOrderCall { params: create_order_object })));
+
+    // ----------------------------------
+    //            Bundling
+    // ----------------------------------
 
-    println!("tx3 ok");
+    let bundle: Vec<Bytes> = encode_bundled_calls(&calls);
+    let tx_data: Bytes = MulticallCall { data: bundle }.encode().into();
+
+    let multicall_transaction: TypedTransaction = TypedTransaction::Eip1559(Eip1559TransactionRequest {
+        from: Some(sender),
+        to: Some(NameOrAddress::Address(exchange_router_address)),
+        value: Some(weth_amount),
+        data: Some(tx_data),
+        chain_id: Some(ARBITRUM_CHAIN_ID.into()),
+        ..Default::default()
+    });
+
+    Ok(OrderTransactionBundle {
+        approval,
+        calls,
+        multicall_transaction,
+    })
+}
+
+fn encode_bundled_calls(calls: &[BundledCall]) -> Vec<Bytes> {
+    calls
+        .iter()
+        .map(|call| match call {
+            BundledCall::SendWnt(call) => call.clone().encode().into(),
+            BundledCall::SendTokens(call) => call.clone().encode().into(),
+            BundledCall::CreateOrder(call) => call.as_ref().clone().encode().into(),
+        })
+        .collect()
+}
+
+// Send phase: approves the Router if the allowance is short, then submits the multicall
+pub async fn send_order_transactions(bundle: OrderTransactionBundle, settings: &TransactionSettings) -> Result<OrderSubmission, Box<dyn std::error::Error>> {
+    let wallet = get_local_signer()?;
+    let provider: Provider<Http> = connect_provider().await?;
+    let arc_provider: Arc<Provider<Http>> = Arc::new(provider);
+    let client: SignerClient = SignerMiddleware::new(arc_provider.clone(), wallet.clone());
+
+    if bundle.multicall_transaction.from() != Some(&wallet.address()) {
+        return Err("Order transactions were built for a different sender than the local signer".into());
+    }
+
+    let create_order: &CreateOrderCall = bundle.calls
+        .iter()
+        .find_map(|call| match call {
+            BundledCall::CreateOrder(create_order) => Some(create_order.as_ref()),
+            _ => None,
+        })
+        .ok_or("Order transactions have no createOrder call")?;
+    let execution_fee: U256 = create_order.params.numbers.execution_fee;
+    let order_type: OrderType = OrderType::try_from(create_order.params.order_type)?;
+    println!("EXECUTION FEE = {}", execution_fee);
+    println!("WNT AMOUNT = {}", bundle.multicall_transaction.value().copied().unwrap_or_default());
 
     // ----------------------------------
     //            Approval Tx
     // ----------------------------------
 
-    let approval_gas: U256 = U256::from(2000000);
-    let gas_price: U256 = get_current_gas_price().await?;
-    let approval_nonce: U256 = arc_provider.clone().get_transaction_count(wallet.clone().address(), None).await
-    .map_err(|e| format!("Error fetching nonce: {}", e))?;
-    let approval_tx_request: TransactionRequest = TransactionRequest {
-        from: Some(wallet.address()),
-        to: Some(NameOrAddress::Address(approval_contract)),
-        gas: Some(approval_gas),
-        gas_price: Some(gas_price),
-        nonce: Some(approval_nonce),
-        data: Some(tx0_bytes.into()),
-        value: Some(0.into()),
-        chain_id: Some(42161.into())
-    };
-
-    let access_list: Vec<_> = Vec::new();
-    let priority_fee: U256 = U256::from(100000000);
-    let max_fee_per_gas: U256 = gas_price + priority_fee;
-    let typed_approval_tx: Eip1559TransactionRequest = ethers::types::transaction::eip1559::Eip1559TransactionRequest {
-        from: approval_tx_request.from,
-        to: approval_tx_request.to,
-        nonce: approval_tx_request.nonce,
-        max_priority_fee_per_gas: Some(priority_fee),
-        max_fee_per_gas: Some(max_fee_per_gas),
-        gas: approval_tx_request.gas,
-        value: approval_tx_request.value,
-        data: approval_tx_request.data,
-        access_list: ethers::types::transaction::eip2930::AccessList(access_list.clone()),
-        chain_id: approval_tx_request.chain_id,
+    let approval_receipt: Option<TransactionReceipt> = match &bundle.approval {
+        Some(approval) => send_approval_if_needed(&client, arc_provider.clone(), approval, settings).await?,
+        None => None,
     };
-    
-    let typed_approval_tx: TypedTransaction = TypedTransaction::Eip1559(typed_approval_tx);
-    let pending_approval_tx: PendingTransaction<'_, Http> = client.send_transaction(typed_approval_tx, None).await?;
-    let receipt: Option<TransactionReceipt> = pending_approval_tx.confirmations(1).await?;
 
-    println!("Approval Tx submitted to network. receipt: {:?}", receipt);
+    // ----------------------------------
+    //      Execution Simulation
+    // ----------------------------------
 
+    // Limit and trigger orders would fail the simulation on their trigger price, so only market orders are executed.
+    // It runs after the approval because sendTokens needs the allowance.
+    let simulates_execution: bool = settings.simulate && order_type.is_market();
+    if simulates_execution {
+        simulate_order_execution(&client, &bundle).await?;
+    }
 
     // ----------------------------------
-    //      Bundling & Tx Execution 
+    //      Multicall Execution
     // ----------------------------------
 
-    let bundle: Vec<Bytes> = vec!(tx1_bytes, tx2_bytes, tx3_bytes);
-
-    let gas_estimate: U256 = U256::from(4000000);
-    println!("Estimated Gas: {}", gas_estimate);
-    let gas_limit: U256 = gas_estimate + 100000; // Buffer
-    println!("GAS LIMIT = {}", gas_limit);
-    let nonce: U256 = arc_provider.clone().get_transaction_count(wallet.clone().address(), None).await
-    .map_err(|e| format!("Error fetching nonce: {}", e))?;
-
-    // Step 1: Prepare the Transaction Request
-    let tx_data = contracts.exchange_router_contract.multicall(bundle.clone()).calldata().unwrap();
-    let tx_request: TransactionRequest = TransactionRequest {
-        from: Some(wallet.address()),
-        to: Some(NameOrAddress::Address(contracts.exchange_router_contract.address())),
-        gas: Some(gas_limit),
-        gas_price: Some(gas_price),
-        nonce: Some(nonce),
-        data: Some(tx_data.into()),
-        value: execution_fee.into(),
-        chain_id: Some(42161.into())
+    // The execution simulation already ran createOrder, so the multicall skips its own eth_call
+    let multicall_settings: TransactionSettings = TransactionSettings {
+        simulate: settings.simulate && !simulates_execution,
+        ..settings.clone()
     };
+    let receipt: Option<TransactionReceipt> = send_typed_transaction(&client, bundle.multicall_transaction, &multicall_settings).await?;
 
-    // Step 2: Convert into TypedTransaction + define gas
-    let max_fee_per_gas: U256 = gas_price + priority_fee;
-    let typed_tx: Eip1559TransactionRequest = ethers::types::transaction::eip1559::Eip1559TransactionRequest {
-        from: tx_request.from,
-        to: tx_request.to,
-        nonce: tx_request.nonce,
-        max_priority_fee_per_gas: Some(priority_fee),
-        max_fee_per_gas: Some(max_fee_per_gas),
-        gas: tx_request.gas,
-        value: tx_request.value,
-        data: tx_request.data,
-        access_list: ethers::types::transaction::eip2930::AccessList(access_list.clone()),
-        chain_id: tx_request.chain_id,
-    };
-    
-    let typed_tx: TypedTransaction = TypedTransaction::Eip1559(typed_tx);
+    println!("Order Tx submitted to network, receipt: {:?}", receipt);
 
-    // Step 3: Sign and Send the Transaction
-    let pending_tx: PendingTransaction<'_, Http> = client.send_transaction(typed_tx, None).await?;
-    let receipt: Option<TransactionReceipt> = pending_tx.confirmations(1).await?;
+    let receipt: TransactionReceipt = receipt.ok_or("Order transaction was dropped before confirmation")?;
+    let order_key: H256 = get_order_key_from_receipt(&receipt)?;
+    println!("Order key: {:?}", order_key);
 
-    println!("Order Tx submitted to network, receipt: {:?}", receipt);
+    // ----------------------------------
+    //         Submission Summary
+    // ----------------------------------
+
+    let gas_used: U256 = receipt.gas_used.unwrap_or_default();
+    let effective_gas_price: U256 = receipt.effective_gas_price.unwrap_or_default();
+    let approval_gas_cost: U256 = approval_receipt
+        .as_ref()
+        .map(|approval| approval.gas_used.unwrap_or_default() * approval.effective_gas_price.unwrap_or_default())
+        .unwrap_or_default();
+    let total_cost_wei: U256 = gas_used * effective_gas_price + approval_gas_cost + execution_fee;
+
+    let submission: OrderSubmission = OrderSubmission {
+        order_key,
+        approval_tx_hash: approval_receipt.map(|approval| approval.transaction_hash),
+        order_tx_hash: receipt.transaction_hash,
+        block_number: receipt.block_number.ok_or("Order receipt has no block number")?,
+        gas_used,
+        effective_gas_price,
+        total_cost_wei,
+        total_cost_eth: format_ether(total_cost_wei),
+    };
+    println!("Order submission: {:?}", submission);
+
+    Ok(submission)
+}
+
+// Runs the multicall with ExchangeRouter.simulateExecuteOrder appended, as the GMX UI does before submitting.
+// The order key is predicted from the DataStore nonce, and every token is priced from one API fetch.
+// The call always reverts: EndOfOracleSimulation means the order executed, any other error is why it would not.
+async fn simulate_order_execution(client: &SignerClient, bundle: &OrderTransactionBundle) -> Result<(), Box<dyn std::error::Error>> {
+    let contracts: Contracts = Contracts::new(client.inner().clone());
+    let keys: KeyHashes = KeyHashes::new();
+    let data_store: Address = contracts.data_store_contract.address();
+
+    // The nonce and the simulation are pinned to one block so the predicted key matches the order createOrder makes
+    let block_number: U64 = client.get_block_number().await?;
+    let nonce: U256 = contracts.data_store_contract
+        .get_uint(H256::from_slice(&hex::decode(&keys.nonce)?).to_fixed_bytes())
+        .block(block_number)
+        .call()
+        .await?;
+
+    // createOrder increments the nonce and keys the order as keccak256(abi.encode(dataStore, nonce))
+    let order_key: H256 = H256::from_slice(&hex::decode(hash_data(
+        vec!["address", "uint256"],
+        vec![AbiToken::Address(data_store), AbiToken::Uint(nonce + 1)],
+    ))?);
+
+    let prices: HashMap<Address, TokenPriceFromApiResponse> = fetch_token_prices_by_address().await?;
+    let mut primary_tokens: Vec<Address> = Vec::new();
+    let mut primary_prices: Vec<PriceProps> = Vec::new();
+    for (token, price) in prices {
+        match (U256::from_dec_str(&price.min_price_full), U256::from_dec_str(&price.max_price_full)) {
+            (Ok(min), Ok(max)) if !min.is_zero() && !max.is_zero() => {
+                primary_tokens.push(token);
+                primary_prices.push(PriceProps { min, max });
+            },
+            _ => continue,
+        }
+    }
+
+    let mut calls: Vec<Bytes> = encode_bundled_calls(&bundle.calls);
+    calls.push(SimulateExecuteOrderCall {
+        key: order_key.to_fixed_bytes(),
+        simulated_oracle_params: SimulatePricesParams { primary_tokens, primary_prices },
+    }.encode().into());
+
+    let mut simulation_tx: TypedTransaction = bundle.multicall_transaction.clone();
+    simulation_tx.set_data(MulticallCall { data: calls }.encode().into());
+
+    match client.call(&simulation_tx, Some(BlockId::Number(block_number.into()))).await {
+        Ok(_) => println!("Execution simulation ok"),
+        Err(e) => match to_preflight_error(&e) {
+            PreflightError::Reverted { error: GmxError::EndOfOracleSimulation, .. } => println!("Execution simulation ok"),
+            preflight_error => return Err(preflight_error.into()),
+        },
+    }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract_caller::utils::structs::{Markets, OrderObjectBuilder, OrderType};
+
+    const SENDER: &str = "0x729fBbB8a11Cf2d564Ba5Fd913AEdEf1D9a6ea66";
+
+    // multicall([sendWnt(vault, fee), sendTokens(USDC, vault, 100 USDC), createOrder(params)]) for usdc_market_increase()
+    const USDC_MARKET_INCREASE_CALLDATA: &str = concat!(
+        "ac9650d8",
+        "0000000000000000000000000000000000000000000000000000000000000020",
+        "0000000000000000000000000000000000000000000000000000000000000003",
+        "0000000000000000000000000000000000000000000000000000000000000060",
+        "00000000000000000000000000000000000000000000000000000000000000e0",
+        "0000000000000000000000000000000000000000000000000000000000000180",
+        "0000000000000000000000000000000000000000000000000000000000000044",
+        "7d39aaf100000000000000000000000031ef83a530fde1b38ee9a18093a333d8",
+        "bbbc40d50000000000000000000000000000000000000000000000000000b5e6",
+        "20f4800000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000064",
+        "e6d66ac8000000000000000000000000af88d065e77c8cc2239327c5edb3a432",
+        "268e583100000000000000000000000031ef83a530fde1b38ee9a18093a333d8",
+        "bbbc40d500000000000000000000000000000000000000000000000000000000",
+        "05f5e10000000000000000000000000000000000000000000000000000000000",
+        "00000000000000000000000000000000000000000000000000000000000002a4",
+        "4a393a4100000000000000000000000000000000000000000000000000000000",
+        "0000002000000000000000000000000000000000000000000000000000000000",
+        "000001a0000000000000000000000000000000000000314dc6448d9338c15b0a",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "00000000000000000000000000000000000000000000000000000000000c6f3b",
+        "40b6c0000000000000000000000000000000000000000000000000000000b5e6",
+        "20f4800000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000200000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000100000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "00000000000000000000000000000000729fbbb8a11cf2d564ba5fd913aedef1",
+        "d9a6ea6600000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000070d95587d40a2caf56bd97485ab3eec1",
+        "0bee6336000000000000000000000000af88d065e77c8cc2239327c5edb3a432",
+        "268e583100000000000000000000000000000000000000000000000000000000",
+        "000000c000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+    );
+
+    fn address(address: &str) -> Address {
+        address.parse().expect("Invalid H160 address")
+    }
+
+    // $1000 long on the ETH market backed by 100 USDC, accepting up to $3500. Tests change one field on top of it.
+    fn usdc_market_increase() -> OrderObjectBuilder {
+        OrderObjectBuilder::new(OrderType::MarketIncrease)
+            .is_long(true)
+            .amount(U256::from(100_000_000u64))
+            .receiver(address(SENDER))
+            .market(address(&Markets::ETH.info().market_address))
+            .initial_collateral_token(address(Token::USDC.info().address))
+            .size_delta_usd(U256::from(1000u64) * U256::exp10(30))
+            .acceptable_price(U256::from(3500u64) * U256::exp10(12))
+            .execution_fee(U256::from(200_000_000_000_000u64))
+    }
+
+    #[test]
+    fn usdc_collateral_encodes_known_multicall() {
+        let order: OrderObject = usdc_market_increase().build().expect("Invalid test order");
+        let bundle: OrderTransactionBundle = build_order_transactions(&order, address(SENDER), ApprovalAmount::Exact)
+            .expect("Failed to build order transactions");
+
+        let expected: Vec<u8> = hex::decode(USDC_MARKET_INCREASE_CALLDATA).expect("Invalid hex string");
+        assert_eq!(bundle.multicall_transaction.data().map(|data| data.to_vec()), Some(expected));
+        assert_eq!(bundle.multicall_transaction.value(), Some(&U256::from(200_000_000_000_000u64)));
+        assert_eq!(bundle.multicall_transaction.to(), Some(&NameOrAddress::Address(address(EXCHANGE_ROUTER_ADDRESS))));
+
+        let approval: ApprovalRequest = bundle.approval.expect("USDC collateral needs a Router approval");
+        assert_eq!(approval.token, Token::USDC);
+        assert_eq!(approval.approve_amount, U256::from(100_000_000u64));
+    }
+
+    #[test]
+    fn native_eth_collateral_rides_on_send_wnt() {
+        let order: OrderObject = usdc_market_increase()
+            .initial_collateral_token(address(Token::ETH.info().address))
+            .build()
+            .expect("Invalid test order");
+
+        let bundle: OrderTransactionBundle = build_order_transactions(&order, address(SENDER), ApprovalAmount::Exact)
+            .expect("Failed to build order transactions");
+        let wnt_amount: U256 = U256::from(100_000_000u64) + U256::from(200_000_000_000_000u64);
+
+        assert!(bundle.approval.is_none());
+        assert_eq!(bundle.calls.len(), 2);
+        assert!(matches!(&bundle.calls[0], BundledCall::SendWnt(call) if call.amount == wnt_amount));
+        assert!(matches!(&bundle.calls[1], BundledCall::CreateOrder(_)));
+        assert_eq!(bundle.multicall_transaction.value(), Some(&wnt_amount));
+    }
+}
@@ -0,0 +1,50 @@
+use ethers::prelude::*;
+use ethers::types::{H160, H256, U256};
+use dotenv::dotenv;
+use anyhow::anyhow;
+use std::sync::Arc;
+
+use super::utils::local_signer::get_local_signer;
+use super::utils::contract_addresses::Contracts;
+use super::utils::structs::TransactionSettings;
+use super::utils::transaction_sender::{send_eip1559_transaction, SignerClient};
+
+use crate::contract_caller::connect_provider::connect_provider;
+
+
+// Amends a resting limit, trigger or limit swap order in place. Values are in contract units, the same as
+// OrderObject: USD values and prices use 30 decimals, min_output_amount is in output token units.
+pub async fn update_order(
+    order_key: H256,
+    size_delta_usd: U256,
+    acceptable_price: U256,
+    trigger_price: U256,
+    min_output_amount: U256,
+) -> Result<TransactionReceipt, Box<dyn std::error::Error>> {
+    dotenv().ok();
+
+    let wallet = get_local_signer()?;
+    let provider: Provider<Http> = connect_provider().await?;
+    let arc_provider: Arc<Provider<Http>> = Arc::new(provider);
+    let contracts: Contracts = Contracts::new(arc_provider.clone());
+    let client: SignerClient = SignerMiddleware::new(arc_provider.clone(), wallet.clone());
+
+    println!("Updating order {:?}", order_key);
+
+    let exchange_router_address: H160 = contracts.exchange_router_contract.address();
+    let tx_data: Bytes = contracts.exchange_router_contract
+        .update_order(order_key.to_fixed_bytes(), size_delta_usd, acceptable_price, trigger_price, min_output_amount)
+        .calldata()
+        .ok_or_else(|| anyhow!("Failed to build updateOrder calldata"))?;
+
+    let receipt: Option<TransactionReceipt> = send_eip1559_transaction(&client, exchange_router_address, tx_data, U256::zero(), &TransactionSettings::default()).await?;
+
+    println!("Update Tx submitted to network, receipt: {:?}", receipt);
+
+    let receipt: TransactionReceipt = receipt.ok_or("Update transaction was dropped before confirmation")?;
+    if receipt.status != Some(U64::one()) {
+        return Err(format!("Update transaction {:?} reverted", receipt.transaction_hash).into());
+    }
+
+    Ok(receipt)
+}
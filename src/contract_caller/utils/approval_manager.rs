@@ -0,0 +1,111 @@
+use ethers::prelude::*;
+use ethers::abi::AbiEncode;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{H160, U256, NameOrAddress};
+use std::sync::Arc;
+
+use super::contract_addresses::ARBITRUM_CHAIN_ID;
+use super::structs::{ApprovalRequest, Token, TokenInfo, TransactionSettings};
+use super::transaction_sender::{send_typed_transaction, SignerClient};
+
+// Every collateral token is a plain ERC20 as far as approvals go, so one binding covers the whole Token table
+abigen!(
+    ERC20,
+    r#"[
+        function allowance(address owner, address spender) external view returns (uint256)
+        function approve(address spender, uint256 amount) external returns (bool)
+    ]"#
+);
+
+// ExchangeRouter.sendTokens pulls collateral through Router.pluginTransfer, so the Router is the spender
+pub const ROUTER_ADDRESS: &str = "0x7452c558d45f8afc8c83dae62c3f8a5be19c71f6";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ApprovalAmount {
+    // Approve exactly what this order needs
+    Exact,
+    // Approve up to a standing limit so later orders can skip the approval transaction
+    Capped(U256),
+}
+
+pub fn router_address() -> H160 {
+    ROUTER_ADDRESS.parse().expect("Invalid H160 address")
+}
+
+pub async fn get_router_allowance(provider: Arc<Provider<Http>>, token: Token, owner: H160) -> Result<U256, Box<dyn std::error::Error>> {
+    let token_contract: ERC20<Provider<Http>> = ERC20::new(token_address(token)?, provider);
+    let allowance: U256 = token_contract.allowance(owner, router_address()).call().await?;
+
+    Ok(allowance)
+}
+
+// Builds the approve transaction for the Router without touching the network. Fees, gas and nonce are
+// left for the send phase, which also skips the approval if the allowance already covers the amount.
+pub fn build_router_approval(
+    token: Token,
+    owner: H160,
+    required_amount: U256,
+    approval_amount: ApprovalAmount,
+) -> Result<ApprovalRequest, Box<dyn std::error::Error>> {
+    let token_info: TokenInfo = token.info();
+
+    let approve_amount: U256 = match approval_amount {
+        ApprovalAmount::Exact => required_amount,
+        ApprovalAmount::Capped(cap) => {
+            if cap < required_amount {
+                return Err(format!("Approval cap {} is below the required {} {}", cap, required_amount, token_info.name).into());
+            }
+            cap
+        },
+    };
+
+    let approve_bytes: Bytes = ApproveCall { spender: router_address(), amount: approve_amount }.encode().into();
+    let transaction: TypedTransaction = TypedTransaction::Eip1559(Eip1559TransactionRequest {
+        from: Some(owner),
+        to: Some(NameOrAddress::Address(token_address(token)?)),
+        value: Some(U256::zero()),
+        data: Some(approve_bytes),
+        chain_id: Some(ARBITRUM_CHAIN_ID.into()),
+        ..Default::default()
+    });
+
+    Ok(ApprovalRequest {
+        token,
+        required_amount,
+        approve_amount,
+        transaction,
+    })
+}
+
+// Sends the approval only when the Router's current allowance does not already cover the required amount.
+// Returns the approval receipt, or None when no approval was needed.
+pub async fn send_approval_if_needed(
+    client: &SignerClient,
+    provider: Arc<Provider<Http>>,
+    approval: &ApprovalRequest,
+    settings: &TransactionSettings,
+) -> Result<Option<TransactionReceipt>, Box<dyn std::error::Error>> {
+    let token_name: &str = approval.token.info().name;
+
+    let current_allowance: U256 = get_router_allowance(provider, approval.token, client.address()).await?;
+    if current_allowance >= approval.required_amount {
+        println!("Router allowance for {} already covers {} ({})", token_name, approval.required_amount, current_allowance);
+        return Ok(None);
+    }
+    println!("Approving Router for {} {}", approval.approve_amount, token_name);
+
+    let receipt: Option<TransactionReceipt> = send_typed_transaction(client, approval.transaction.clone(), settings).await?;
+    println!("Approval Tx submitted to network. receipt: {:?}", receipt);
+
+    Ok(receipt)
+}
+
+fn token_address(token: Token) -> Result<H160, Box<dyn std::error::Error>> {
+    if token.is_synthetic() {
+        return Err(format!("{} is a synthetic index token and cannot be approved", token.info().name).into());
+    }
+    let token_address: H160 = token.info().address.parse()
+        .map_err(|e| format!("Error parsing token address: {}", e))?;
+
+    Ok(token_address)
+}
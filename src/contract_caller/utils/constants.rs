@@ -0,0 +1,5 @@
+// Denominator for every basis point value: slippage, fee and gas multipliers, leverage
+pub const BPS_DIVISOR: u32 = 10000;
+
+// GMX keeps USD values, prices and factors with 30 decimals
+pub const FLOAT_PRECISION: usize = 30;
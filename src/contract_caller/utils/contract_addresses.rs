@@ -16,8 +16,17 @@ abigen!{
     WBTC, "/Users/jfeasby/GMX Rust/GMX_Rust/src/contract_caller/abis/wbtc_abi.json";
     SOL, "/Users/jfeasby/GMX Rust/GMX_Rust/src/contract_caller/abis/wsol_abi.json";
     GAS_UTILS, "/Users/jfeasby/GMX Rust/GMX_Rust/src/contract_caller/abis/gas_utils_abi.json";
+    DATA_STORE, "/Users/jfeasby/GMX Rust/GMX_Rust/src/contract_caller/abis/data_store_abi.json";
+    // reader_abi.json names its library structs PositionProps, OrderNumbers, PriceProps and so on instead of
+    // Position.Props or Order.Numbers, otherwise abigen collapses every Props, Addresses, Numbers and Flags into one type
+    READER, "/Users/jfeasby/GMX Rust/GMX_Rust/src/contract_caller/abis/reader_abi.json";
 }
 
+pub const EXCHANGE_ROUTER_ADDRESS: &str = "0x7C68C7866A64FA2160F78EEaE12217FFbf871fa8";
+pub const ORDER_VAULT_ADDRESS: &str = "0x31eF83a530Fde1B38EE9A18093A333D8Bbbc40D5";
+
+pub const ARBITRUM_CHAIN_ID: u64 = 42161;
+
 pub struct Contracts {
     pub usdc_contract: USDC_NATIVE<Provider<Http>>,
     pub dai_contract: DAI<Provider<Http>>,
@@ -33,7 +42,8 @@ pub struct Contracts {
     pub order_vault_contract: ORDER_VAULT<Provider<Http>>,
     pub deposit_vault_contract: DEPOSIT_VAULT<Provider<Http>>,
     pub gas_contract: GAS_UTILS<Provider<Http>>,
-    // pub reader_contract: READER<Provider<Http>>
+    pub data_store_contract: DATA_STORE<Provider<Http>>,
+    pub reader_contract: READER<Provider<Http>>,
 }
 
 impl Contracts {
@@ -49,28 +59,12 @@ impl Contracts {
             sol_contract: SOL::<Provider<Http>>::new("0x53B56de645B9de6e5a40acE047D1c74E8B42Eccb".parse::<H160>().unwrap(), provider.clone()),
             usdt_contract: USDT::<Provider<Http>>::new("0xFd086bC7CD5C481DCC9C85ebE478A1C0b69FCbb9".parse::<H160>().unwrap(), provider.clone()),
             usdce_contract: USDCE::<Provider<Http>>::new("0xFF970A61A04b1cA14834A43f5dE4533eBDDB5CC8".parse::<H160>().unwrap(), provider.clone()),
-            exchange_router_contract: EXCHANGE_ROUTER::<Provider<Http>>::new("0x7C68C7866A64FA2160F78EEaE12217FFbf871fa8".parse::<H160>().unwrap(), provider.clone()),
-            order_vault_contract: ORDER_VAULT::<Provider<Http>>::new("0x31eF83a530Fde1B38EE9A18093A333D8Bbbc40D5".parse::<H160>().unwrap(), provider.clone()),
+            exchange_router_contract: EXCHANGE_ROUTER::<Provider<Http>>::new(EXCHANGE_ROUTER_ADDRESS.parse::<H160>().unwrap(), provider.clone()),
+            order_vault_contract: ORDER_VAULT::<Provider<Http>>::new(ORDER_VAULT_ADDRESS.parse::<H160>().unwrap(), provider.clone()),
             deposit_vault_contract: DEPOSIT_VAULT::<Provider<Http>>::new("0xF89e77e8Dc11691C9e8757e84aaFbCD8A67d7A55".parse::<H160>().unwrap(), provider.clone()),
             gas_contract: GAS_UTILS::<Provider<Http>>::new("0x6Ee83F82757C5B10468855753F5374FFF826BDCB".parse::<H160>().unwrap(), provider.clone()),
-            // reader_contract: READER::<Provider<Http>>::new("0xf60becbba223eea9495da3f606753867ec10d139".parse::<H160>().unwrap(), provider.clone()),
-        }
-    }
-
-    // Dynamically select and call approve function based on token
-    pub async fn approve(&self, token: &str, spender: Address, amount: U256) -> Result<Bytes, Box<dyn std::error::Error>> {
-        match token {
-            "USDC" => Ok(self.usdc_contract.approve(spender, amount).calldata().unwrap()),
-            "DAI" => Ok(self.dai_contract.approve(spender, amount).calldata().unwrap()),
-            "WETH" => Ok(self.weth_contract.approve(spender, amount).calldata().unwrap()),
-            "WBTC" => Ok(self.wbtc_contract.approve(spender, amount).calldata().unwrap()),
-            "LINK" => Ok(self.link_contract.approve(spender, amount).calldata().unwrap()),
-            "ARB" => Ok(self.arb_contract.approve(spender, amount).calldata().unwrap()),
-            "UNI" => Ok(self.uni_contract.approve(spender, amount).calldata().unwrap()),
-            "SOL" => Ok(self.sol_contract.approve(spender, amount).calldata().unwrap()),
-            "USDT" => Ok(self.usdt_contract.approve(spender, amount).calldata().unwrap()),
-            "USDCE" => Ok(self.usdce_contract.approve(spender, amount).calldata().unwrap()),
-            _ => Err("Unsupported token".into()),
+            data_store_contract: DATA_STORE::<Provider<Http>>::new("0xFD70de6b91282D8017aA4E741e9Ae325CAb992d8".parse::<H160>().unwrap(), provider.clone()),
+            reader_contract: READER::<Provider<Http>>::new("0xf60becbba223eea9495da3f606753867ec10d139".parse::<H160>().unwrap(), provider.clone()),
         }
     }
 }
\ No newline at end of file
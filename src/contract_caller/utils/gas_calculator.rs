@@ -1,26 +1,79 @@
 use reqwest;
-use ethers::core::types::U256;
-use super::{structs::GasPriceResponse};
+use ethers::core::types::{H256, U256};
+use ethers::prelude::{BlockNumber, FeeHistory, Http, Middleware, Provider};
+use super::{structs::{Eip1559Fees, FeeStrategy, GasPriceResponse, KeyHashes, OrderType}};
+use super::constants::{BPS_DIVISOR, FLOAT_PRECISION};
+use super::contract_addresses::Contracts;
+use crate::contract_caller::connect_provider::connect_provider;
 use std::env;
+use std::sync::Arc;
 
-pub async fn calculate_execution_fee(gas_estimate: u64) -> Result<U256, Box<dyn std::error::Error>> {
+// Keeper fees are charged against tx.gasprice at creation, so market orders only need a small cushion.
+// Resting orders can wait through gas spikes before a keeper picks them up. Unused fee is refunded.
+pub const MARKET_ORDER_FEE_MULTIPLIER_BPS: u32 = 12000;
+pub const RESTING_ORDER_FEE_MULTIPLIER_BPS: u32 = 15000;
+
+// Arbitrum orders rarely need more than a nominal tip to be included
+pub const DEFAULT_PRIORITY_FEE: u64 = 100000000;
+
+// Mirrors GasUtils.estimateExecuteOrderGasLimit and adjustGasLimitForEstimate in the GMX contracts:
+// gas_limit = base + (order_gas_limit + single_swap_gas_limit * swaps + callback_gas_limit) * multiplier / 10^30
+pub async fn calculate_execution_fee(
+    order_type: OrderType,
+    swap_path_length: usize,
+    callback_gas_limit: U256,
+    safety_multiplier_bps: u32,
+) -> Result<U256, Box<dyn std::error::Error>> {
     println!("Calculating execution fee...");
 
-    // Fetch the current gas price
-    println!("Fetching current gas price...");
-    let current_gas_price: U256 = get_current_gas_price().await?;
-    println!("Current gas price: {}", current_gas_price);
+    let provider: Provider<Http> = connect_provider().await?;
+    let contracts: Contracts = Contracts::new(Arc::new(provider));
+    let keys: KeyHashes = KeyHashes::new();
+
+    let base_gas_limit: U256 = get_data_store_uint(&contracts, &keys.estimated_gas_fee_base_amount).await?;
+    let multiplier_factor: U256 = get_data_store_uint(&contracts, &keys.estimated_gas_fee_multiplier_factor).await?;
+    let single_swap_gas_limit: U256 = get_data_store_uint(&contracts, &keys.single_swap_gas_limit).await?;
+    let order_gas_limit_key: &str = if order_type.is_swap() {
+        &keys.swap_order_gas_limit
+    } else if order_type.is_increase() {
+        &keys.increase_order_gas_limit
+    } else {
+        &keys.decrease_order_gas_limit
+    };
+    let order_gas_limit: U256 = get_data_store_uint(&contracts, order_gas_limit_key).await?;
 
-    // Calculate the execution fee
-    println!("Calculating execution fee with gas estimate: {}", gas_estimate);
-    let execution_fee: U256 = current_gas_price.checked_mul(U256::from(gas_estimate))
-        .ok_or_else(|| "Execution fee overflow")?;
+    let estimated_gas_limit: U256 = order_gas_limit
+        + single_swap_gas_limit * U256::from(swap_path_length)
+        + callback_gas_limit;
+    let adjusted_gas_limit: U256 = base_gas_limit
+        + estimated_gas_limit
+            .checked_mul(multiplier_factor)
+            .ok_or("Gas limit adjustment overflow")?
+            / U256::exp10(FLOAT_PRECISION);
+    println!("Estimated execution gas limit: {}", adjusted_gas_limit);
 
+    let current_gas_price: U256 = get_current_gas_price().await?;
+    let min_execution_fee: U256 = adjusted_gas_limit
+        .checked_mul(current_gas_price)
+        .ok_or("Execution fee overflow")?;
+    println!("Minimum execution fee: {}", min_execution_fee);
+
+    let execution_fee: U256 = min_execution_fee
+        .checked_mul(U256::from(safety_multiplier_bps.max(BPS_DIVISOR)))
+        .ok_or("Execution fee overflow")?
+        / U256::from(BPS_DIVISOR);
     println!("Calculated execution fee: {}", execution_fee);
 
     Ok(execution_fee)
 }
 
+pub async fn get_data_store_uint(contracts: &Contracts, key_hash: &str) -> Result<U256, Box<dyn std::error::Error>> {
+    let key: H256 = H256::from_slice(&hex::decode(key_hash)?);
+    let value: U256 = contracts.data_store_contract.get_uint(key.to_fixed_bytes()).call().await?;
+
+    Ok(value)
+}
+
 pub async fn get_current_gas_price() -> Result<U256, Box<dyn std::error::Error>> {
     println!("Fetching current gas price...");
 
@@ -54,3 +107,53 @@ pub async fn get_current_gas_price() -> Result<U256, Box<dyn std::error::Error>>
 
     Ok(gas_price_wei)
 }
+
+pub async fn resolve_eip1559_fees<M: Middleware>(client: &M, strategy: &FeeStrategy) -> Result<Eip1559Fees, Box<dyn std::error::Error>> {
+    let fees: Eip1559Fees = match strategy {
+        FeeStrategy::FeeHistory { block_count, reward_percentile, base_fee_multiplier_bps } => {
+            let fee_history: FeeHistory = client
+                .fee_history(*block_count, BlockNumber::Latest, &[*reward_percentile])
+                .await
+                .map_err(|e| format!("Error fetching fee history: {}", e))?;
+
+            // The last base fee entry is the one predicted for the next block
+            let next_base_fee: U256 = *fee_history.base_fee_per_gas.last()
+                .ok_or("Fee history returned no base fees")?;
+
+            let mut rewards: Vec<U256> = fee_history.reward
+                .iter()
+                .filter_map(|block_rewards| block_rewards.first().copied())
+                .collect();
+            rewards.sort();
+            let max_priority_fee_per_gas: U256 = rewards.get(rewards.len() / 2).copied().unwrap_or_default();
+
+            let max_fee_per_gas: U256 = next_base_fee
+                .checked_mul(U256::from(*base_fee_multiplier_bps))
+                .ok_or("Max fee overflow")?
+                / U256::from(BPS_DIVISOR)
+                + max_priority_fee_per_gas;
+
+            Eip1559Fees { max_fee_per_gas, max_priority_fee_per_gas }
+        },
+        FeeStrategy::Fixed { max_fee_per_gas, max_priority_fee_per_gas } => {
+            if max_priority_fee_per_gas > max_fee_per_gas {
+                return Err("Fixed priority fee cannot exceed the max fee".into());
+            }
+            Eip1559Fees { max_fee_per_gas: *max_fee_per_gas, max_priority_fee_per_gas: *max_priority_fee_per_gas }
+        },
+        FeeStrategy::CappedMax { max_fee_per_gas: cap } => {
+            // Read through the signing client's own provider so this works without the Alchemy key
+            let gas_price: U256 = client
+                .get_gas_price()
+                .await
+                .map_err(|e| format!("Error fetching gas price: {}", e))?;
+            let max_fee_per_gas: U256 = (gas_price + U256::from(DEFAULT_PRIORITY_FEE)).min(*cap);
+            let max_priority_fee_per_gas: U256 = U256::from(DEFAULT_PRIORITY_FEE).min(max_fee_per_gas);
+
+            Eip1559Fees { max_fee_per_gas, max_priority_fee_per_gas }
+        },
+    };
+    println!("Max fee per gas: {}, priority fee: {}", fees.max_fee_per_gas, fees.max_priority_fee_per_gas);
+
+    Ok(fees)
+}
@@ -0,0 +1,134 @@
+use ethers::abi::ethabi::AbiError;
+use ethers::abi::{Contract, ParamType, Token as AbiToken};
+use ethers::types::{Bytes, I256};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+
+use super::structs::GmxError;
+
+// Selectors of the two revert payloads solc emits on its own, Error(string) and Panic(uint256)
+const ERROR_STRING_SELECTOR: [u8; 4] = [0x08, 0xc3, 0x79, 0xa0];
+const PANIC_SELECTOR: [u8; 4] = [0x4e, 0x48, 0x7b, 0x71];
+
+// Every ABI the crate carries that declares custom errors. The contract ABIs only list the errors their own
+// code raises, so errors from the GMX Errors library thrown inside the order handlers and position, swap and
+// oracle libraries (EmptyPosition, OrderNotFulfillableAtAcceptablePrice, EndOfOracleSimulation, ...) are not
+// in any of them. gmx_errors_abi.json declares those, copied from Errors.sol.
+const ERROR_ABIS: [&str; 5] = [
+    include_str!("../abis/exchange_router_abi.json"),
+    include_str!("../abis/data_store_abi.json"),
+    include_str!("../abis/deposit_vault_abi.json"),
+    include_str!("../abis/reader_abi.json"),
+    include_str!("../abis/gmx_errors_abi.json"),
+];
+
+lazy_static! {
+    static ref ERRORS_BY_SELECTOR: HashMap<[u8; 4], AbiError> = {
+        let mut errors: HashMap<[u8; 4], AbiError> = HashMap::new();
+        for abi in ERROR_ABIS {
+            let contract: Contract = Contract::load(abi.as_bytes()).expect("Invalid ABI JSON");
+            for error in contract.errors() {
+                let mut selector: [u8; 4] = [0; 4];
+                selector.copy_from_slice(&error.signature().as_bytes()[..4]);
+                errors.insert(selector, error.clone());
+            }
+        }
+        errors
+    };
+}
+
+pub fn decode_gmx_error(revert_data: &Bytes) -> GmxError {
+    if revert_data.len() < 4 {
+        return GmxError::Revert("execution reverted without a reason".to_string());
+    }
+    let mut selector: [u8; 4] = [0; 4];
+    selector.copy_from_slice(&revert_data[..4]);
+    let payload: &[u8] = &revert_data[4..];
+
+    if selector == ERROR_STRING_SELECTOR {
+        if let Ok(tokens) = ethers::abi::decode(&[ParamType::String], payload) {
+            if let Some(AbiToken::String(reason)) = tokens.into_iter().next() {
+                return GmxError::Revert(reason);
+            }
+        }
+    } else if selector == PANIC_SELECTOR {
+        if let Ok(tokens) = ethers::abi::decode(&[ParamType::Uint(256)], payload) {
+            if let Some(AbiToken::Uint(code)) = tokens.into_iter().next() {
+                return GmxError::Revert(format!("panic code {:#x}", code));
+            }
+        }
+    }
+
+    let error: &AbiError = match ERRORS_BY_SELECTOR.get(&selector) {
+        Some(error) => error,
+        None => return GmxError::Unknown { selector },
+    };
+    let tokens: Vec<AbiToken> = match error.decode(payload) {
+        Ok(tokens) => tokens,
+        Err(_) => return GmxError::Unknown { selector },
+    };
+
+    typed_gmx_error(&error.name, &tokens).unwrap_or_else(|| GmxError::Custom {
+        name: error.name.clone(),
+        args: tokens.iter().map(|token| token.to_string()).collect(),
+    })
+}
+
+// Int arguments decode as raw two's complement words, so they go through I256::from_raw
+fn typed_gmx_error(name: &str, tokens: &[AbiToken]) -> Option<GmxError> {
+    match (name, tokens) {
+        ("InsufficientCollateralAmount", [AbiToken::Uint(collateral_amount), AbiToken::Int(collateral_delta_amount)]) =>
+            Some(GmxError::InsufficientCollateralAmount {
+                collateral_amount: *collateral_amount,
+                collateral_delta_amount: I256::from_raw(*collateral_delta_amount),
+            }),
+        ("InsufficientCollateralUsd", [AbiToken::Int(remaining_collateral_usd)]) =>
+            Some(GmxError::InsufficientCollateralUsd {
+                remaining_collateral_usd: I256::from_raw(*remaining_collateral_usd),
+            }),
+        ("OrderNotFulfillableAtAcceptablePrice", [AbiToken::Uint(price), AbiToken::Uint(acceptable_price)]) =>
+            Some(GmxError::OrderNotFulfillableAtAcceptablePrice { price: *price, acceptable_price: *acceptable_price }),
+        ("DisabledMarket", [AbiToken::Address(market)]) =>
+            Some(GmxError::DisabledMarket { market: *market }),
+        ("EmptyMarket", []) => Some(GmxError::EmptyMarket),
+        ("EndOfOracleSimulation", []) => Some(GmxError::EndOfOracleSimulation),
+        ("InsufficientWntAmountForExecutionFee", [AbiToken::Uint(wnt_amount), AbiToken::Uint(execution_fee)]) =>
+            Some(GmxError::InsufficientWntAmountForExecutionFee { wnt_amount: *wnt_amount, execution_fee: *execution_fee }),
+        ("InsufficientExecutionFee", [AbiToken::Uint(min_execution_fee), AbiToken::Uint(execution_fee)]) =>
+            Some(GmxError::InsufficientExecutionFee { min_execution_fee: *min_execution_fee, execution_fee: *execution_fee }),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::types::U256;
+    use ethers::utils::id;
+
+    fn revert_data(signature: &str, args: &[AbiToken]) -> Bytes {
+        let mut data: Vec<u8> = id(signature).to_vec();
+        data.extend(ethers::abi::encode(args));
+        data.into()
+    }
+
+    #[test]
+    fn end_of_oracle_simulation_decodes_from_errors_library() {
+        let data: Bytes = revert_data("EndOfOracleSimulation()", &[]);
+
+        assert_eq!(decode_gmx_error(&data), GmxError::EndOfOracleSimulation);
+    }
+
+    #[test]
+    fn execution_errors_decode_with_their_arguments() {
+        let data: Bytes = revert_data(
+            "OrderNotFulfillableAtAcceptablePrice(uint256,uint256)",
+            &[AbiToken::Uint(U256::from(2001)), AbiToken::Uint(U256::from(2000))],
+        );
+
+        assert_eq!(
+            decode_gmx_error(&data),
+            GmxError::OrderNotFulfillableAtAcceptablePrice { price: U256::from(2001), acceptable_price: U256::from(2000) },
+        );
+    }
+}
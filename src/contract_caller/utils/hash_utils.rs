@@ -20,3 +20,12 @@ pub fn keccak_string(string: &str) -> String {
     let hash = keccak256(string.as_bytes());
     hex::encode(hash)
 }
+
+// DataStore keys scoped to a market, token or side are keccak(abi.encode(base_key, ...))
+pub fn hash_scoped_key(base_key_hex: &str, mut data_types: Vec<&str>, mut data_values: Vec<Token>) -> String {
+    let base_key: Vec<u8> = hex::decode(base_key_hex).expect("Invalid hex string");
+    data_types.insert(0, "bytes32");
+    data_values.insert(0, Token::FixedBytes(base_key));
+
+    hash_data(data_types, data_values)
+}
@@ -1,6 +1,12 @@
 pub mod contract_addresses;
+pub mod constants;
 pub mod structs;
 pub mod local_signer;
 pub mod gas_calculator;
+pub mod transaction_sender;
+pub mod nonce_manager;
+pub mod gmx_errors;
+pub mod approval_manager;
+pub mod order_events;
 pub mod hash_utils;
 pub mod reader_interface;
\ No newline at end of file
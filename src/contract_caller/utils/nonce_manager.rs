@@ -0,0 +1,43 @@
+use ethers::prelude::{BlockId, BlockNumber, Middleware};
+use ethers::types::{H160, U256};
+use lazy_static::lazy_static;
+use std::collections::HashMap;
+use tokio::sync::Mutex;
+
+// Next nonce to hand out for each signer. Shared across every order task in the process so
+// concurrent submissions from one wallet each get their own nonce instead of all reading the same count.
+lazy_static! {
+    static ref NEXT_NONCES: Mutex<HashMap<H160, U256>> = Mutex::new(HashMap::new());
+}
+
+// Allocates the next nonce for the address, seeding from the pending transaction count the first time
+pub async fn allocate_nonce<M: Middleware>(client: &M, address: H160) -> Result<U256, Box<dyn std::error::Error>> {
+    let mut next_nonces = NEXT_NONCES.lock().await;
+
+    let nonce: U256 = match next_nonces.get(&address) {
+        Some(nonce) => *nonce,
+        None => fetch_pending_nonce(client, address).await?,
+    };
+    next_nonces.insert(address, nonce + U256::one());
+    println!("Allocated nonce {} for {:?}", nonce, address);
+
+    Ok(nonce)
+}
+
+// Re-reads the nonce from the chain after a failed or dropped transaction left a gap in the local sequence
+pub async fn resync_nonce<M: Middleware>(client: &M, address: H160) -> Result<U256, Box<dyn std::error::Error>> {
+    let mut next_nonces = NEXT_NONCES.lock().await;
+
+    let nonce: U256 = fetch_pending_nonce(client, address).await?;
+    next_nonces.insert(address, nonce);
+    println!("Resynced nonce for {:?} to {}", address, nonce);
+
+    Ok(nonce)
+}
+
+async fn fetch_pending_nonce<M: Middleware>(client: &M, address: H160) -> Result<U256, Box<dyn std::error::Error>> {
+    let nonce: U256 = client.get_transaction_count(address, Some(BlockId::Number(BlockNumber::Pending))).await
+    .map_err(|e| format!("Error fetching nonce: {}", e))?;
+
+    Ok(nonce)
+}
@@ -0,0 +1,146 @@
+use ethers::abi::{ParamType, Token as AbiToken};
+use ethers::prelude::*;
+use ethers::types::{Filter, Log, H160, H256, U64};
+use ethers::utils::keccak256;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::contract_caller::connect_provider::connect_provider;
+use super::structs::OrderStatus;
+
+pub const EVENT_EMITTER_ADDRESS: &str = "0xC8ee91A54287DB53897056e12D9819156D3822Fb";
+
+// GMX emits every event through EventEmitter.emitEventLog1/2 with the event name hash in topics[1]
+// and the first topic (the order key for all order events) in topics[2]
+const EVENT_NAME_TOPIC_INDEX: usize = 1;
+const ORDER_KEY_TOPIC_INDEX: usize = 2;
+const WATCHER_POLL_INTERVAL_SECS: u64 = 2;
+
+fn event_emitter_address() -> H160 {
+    EVENT_EMITTER_ADDRESS.parse().expect("Invalid H160 address")
+}
+
+// Indexed strings are stored as the keccak of their raw bytes
+fn event_name_topic(event_name: &str) -> H256 {
+    H256::from(keccak256(event_name.as_bytes()))
+}
+
+// Finds the OrderCreated event in a createOrder receipt and returns the new order's key
+pub fn get_order_key_from_receipt(receipt: &TransactionReceipt) -> Result<H256, Box<dyn std::error::Error>> {
+    let emitter: H160 = event_emitter_address();
+    let order_created: H256 = event_name_topic("OrderCreated");
+
+    receipt.logs
+        .iter()
+        .find(|log| log.address == emitter && log.topics.get(EVENT_NAME_TOPIC_INDEX) == Some(&order_created))
+        .and_then(|log| log.topics.get(ORDER_KEY_TOPIC_INDEX).copied())
+        .ok_or_else(|| "No OrderCreated event found in receipt".into())
+}
+
+// Keys of every order an OrderCancelled event in the receipt confirms as cancelled
+pub fn get_cancelled_order_keys_from_receipt(receipt: &TransactionReceipt) -> Vec<H256> {
+    let emitter: H160 = event_emitter_address();
+    let order_cancelled: H256 = event_name_topic("OrderCancelled");
+
+    receipt.logs
+        .iter()
+        .filter(|log| log.address == emitter && log.topics.get(EVENT_NAME_TOPIC_INDEX) == Some(&order_cancelled))
+        .filter_map(|log| log.topics.get(ORDER_KEY_TOPIC_INDEX).copied())
+        .collect()
+}
+
+// Follows EventEmitter logs from from_block until a keeper executes, cancels or freezes the order
+pub async fn watch_order(order_key: H256, from_block: U64, timeout: Duration) -> Result<OrderStatus, Box<dyn std::error::Error>> {
+    let provider: Provider<Http> = connect_provider().await?;
+    let arc_provider: Arc<Provider<Http>> = Arc::new(provider);
+
+    let order_executed: H256 = event_name_topic("OrderExecuted");
+    let order_cancelled: H256 = event_name_topic("OrderCancelled");
+    let order_frozen: H256 = event_name_topic("OrderFrozen");
+
+    let started: Instant = Instant::now();
+    let mut next_block: U64 = from_block;
+
+    println!("Watching order {:?}", order_key);
+
+    loop {
+        let latest_block: U64 = arc_provider.get_block_number().await?;
+
+        if latest_block >= next_block {
+            let filter: Filter = Filter::new()
+                .address(event_emitter_address())
+                .topic1(vec![order_executed, order_cancelled, order_frozen])
+                .topic2(order_key)
+                .from_block(next_block)
+                .to_block(latest_block);
+            let logs: Vec<Log> = arc_provider.get_logs(&filter).await?;
+
+            for log in logs {
+                let event_name: H256 = match log.topics.get(EVENT_NAME_TOPIC_INDEX) {
+                    Some(topic) => *topic,
+                    None => continue,
+                };
+
+                if event_name == order_executed {
+                    println!("Order executed in tx {:?}", log.transaction_hash);
+                    return Ok(OrderStatus::Executed);
+                }
+
+                let reason: String = decode_event_reason(&log.data).unwrap_or_default();
+                if event_name == order_cancelled {
+                    println!("Order cancelled: {}", reason);
+                    return Ok(OrderStatus::Cancelled { reason });
+                }
+                if event_name == order_frozen {
+                    println!("Order frozen: {}", reason);
+                    return Ok(OrderStatus::Frozen { reason });
+                }
+            }
+
+            next_block = latest_block + U64::one();
+        }
+
+        if started.elapsed() >= timeout {
+            return Err("Timed out waiting for the order to be executed, cancelled or frozen".into());
+        }
+
+        tokio::time::sleep(Duration::from_secs(WATCHER_POLL_INTERVAL_SECS)).await;
+    }
+}
+
+// EventLog data is abi.encode(msgSender, eventName, EventLogData), where EventLogData groups its
+// key/value items by type. The cancel and freeze reason lives in the string items under "reason".
+fn decode_event_reason(data: &Bytes) -> Option<String> {
+    let tokens: Vec<AbiToken> = ethers::abi::decode(
+        &[ParamType::Address, ParamType::String, event_log_data_param_type()],
+        data,
+    ).ok()?;
+
+    let event_data: Vec<AbiToken> = tokens.into_iter().nth(2)?.into_tuple()?;
+    let string_items: Vec<AbiToken> = event_data.into_iter().nth(6)?.into_tuple()?;
+    let items: Vec<AbiToken> = string_items.into_iter().next()?.into_array()?;
+
+    items.into_iter().find_map(|item| {
+        let mut key_value = item.into_tuple()?.into_iter();
+        let key: String = key_value.next()?.into_string()?;
+        let value: String = key_value.next()?.into_string()?;
+        (key == "reason").then_some(value)
+    })
+}
+
+fn event_log_data_param_type() -> ParamType {
+    let item_group = |value: ParamType| ParamType::Tuple(vec![
+        ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::String, value.clone()]))),
+        ParamType::Array(Box::new(ParamType::Tuple(vec![ParamType::String, ParamType::Array(Box::new(value))]))),
+    ]);
+
+    ParamType::Tuple(vec![
+        item_group(ParamType::Address),
+        item_group(ParamType::Uint(256)),
+        item_group(ParamType::Int(256)),
+        item_group(ParamType::Bool),
+        item_group(ParamType::FixedBytes(32)),
+        item_group(ParamType::Bytes),
+        item_group(ParamType::String),
+    ])
+}
@@ -0,0 +1,215 @@
+use ethers::abi::Token as AbiToken;
+use ethers::contract::Multicall;
+use ethers::prelude::{Http, Middleware, Provider};
+use ethers::types::{Address, H160, H256, U256, U64};
+use std::collections::HashMap;
+use std::ops::Range;
+use std::sync::Arc;
+use crate::contract_caller::connect_provider::connect_provider;
+use crate::contract_caller::order_builder::get_price::fetch_token_prices_by_address;
+use crate::contract_caller::utils::contract_addresses::{Contracts, MarketInfo as ReaderMarketInfo, MarketPrices, MarketProps, ARBITRUM_CHAIN_ID};
+use crate::contract_caller::utils::hash_utils::hash_scoped_key;
+use crate::contract_caller::utils::reader_interface::reader_functions::get_market_prices;
+use crate::contract_caller::utils::structs::{KeyHashes, MarketSnapshot, Markets, TokenPriceFromApiResponse};
+
+// Snapshot of a single market from getMarketInfo
+pub async fn get_market_snapshot(market: Markets) -> Result<MarketSnapshot, Box<dyn std::error::Error>> {
+    let provider: Provider<Http> = connect_provider().await?;
+    let block_number: U64 = provider.get_block_number().await?;
+    let contracts: Contracts = Contracts::new(Arc::new(provider));
+    let data_store: Address = contracts.data_store_contract.address();
+    let market_address: H160 = market.info().market_address.parse()
+        .map_err(|e| format!("Error parsing market address: {}", e))?;
+
+    let token_prices: HashMap<Address, TokenPriceFromApiResponse> = fetch_token_prices_by_address().await?;
+
+    let market_props: MarketProps = contracts.reader_contract
+        .get_market(data_store, market_address)
+        .block(block_number)
+        .call()
+        .await?;
+    let market_prices: MarketPrices = get_market_prices(&market_props, &token_prices)
+        .map_err(|e| format!("Cannot price {:?} market: {}", market, e))?;
+    let market_info: ReaderMarketInfo = contracts.reader_contract
+        .get_market_info(data_store, market_prices, market_address)
+        .block(block_number)
+        .call()
+        .await?;
+
+    let mut snapshots: Vec<MarketSnapshot> = build_market_snapshots(&contracts, block_number, vec![(market, market_info)]).await?;
+    snapshots.pop().ok_or("No snapshot built for the market".into())
+}
+
+// Snapshots of every market in Markets::all() from getMarketInfoList, with one price download and every read
+// pinned to one block. getMarketInfoList walks the DataStore market list by index and takes a price for each
+// market in its range, but GMX lists markets this crate has no prices for. So the listed markets are located
+// in the list first and read in runs of consecutive indices, which is a single call when they sit together.
+pub async fn get_market_snapshots() -> Result<Vec<MarketSnapshot>, Box<dyn std::error::Error>> {
+    let provider: Provider<Http> = connect_provider().await?;
+    let block_number: U64 = provider.get_block_number().await?;
+    let contracts: Contracts = Contracts::new(Arc::new(provider));
+    let data_store: Address = contracts.data_store_contract.address();
+    let keys: KeyHashes = KeyHashes::new();
+
+    let token_prices: HashMap<Address, TokenPriceFromApiResponse> = fetch_token_prices_by_address().await?;
+
+    let market_count: U256 = contracts.data_store_contract
+        .get_address_count(H256::from_slice(&hex::decode(&keys.market_list)?).to_fixed_bytes())
+        .block(block_number)
+        .call()
+        .await?;
+    let market_list: Vec<MarketProps> = contracts.reader_contract
+        .get_markets(data_store, U256::zero(), market_count)
+        .block(block_number)
+        .call()
+        .await?;
+    println!("Reading {} of {} markets at block {}", Markets::all().len(), market_list.len(), block_number);
+
+    // Position of each listed market in the DataStore market list, priced from the one download
+    let mut listed_markets: Vec<(usize, Markets, MarketPrices)> = Vec::new();
+    for market in Markets::all() {
+        let market_address: H160 = market.info().market_address.parse()
+            .map_err(|e| format!("Error parsing market address: {}", e))?;
+        let index: usize = market_list.iter()
+            .position(|market_props| market_props.market_token == market_address)
+            .ok_or(format!("{:?} market is not in the DataStore market list", market))?;
+        let market_prices: MarketPrices = get_market_prices(&market_list[index], &token_prices)
+            .map_err(|e| format!("Cannot price {:?} market: {}", market, e))?;
+        listed_markets.push((index, market, market_prices));
+    }
+    listed_markets.sort_by_key(|(index, _, _)| *index);
+
+    let indices: Vec<usize> = listed_markets.iter().map(|(index, _, _)| *index).collect();
+    let mut market_infos: Vec<(Markets, ReaderMarketInfo)> = Vec::new();
+    let mut remaining = listed_markets.into_iter();
+    for range in consecutive_ranges(&indices) {
+        let run: Vec<(usize, Markets, MarketPrices)> = remaining.by_ref().take(range.len()).collect();
+        let run_infos: Vec<ReaderMarketInfo> = contracts.reader_contract
+            .get_market_info_list(
+                data_store,
+                run.iter().map(|(_, _, market_prices)| market_prices.clone()).collect(),
+                U256::from(range.start),
+                U256::from(range.end),
+            )
+            .block(block_number)
+            .call()
+            .await?;
+        if run_infos.len() != run.len() {
+            return Err("getMarketInfoList returned a different number of markets than requested".into());
+        }
+
+        market_infos.extend(run.into_iter().map(|(_, market, _)| market).zip(run_infos));
+    }
+
+    build_market_snapshots(&contracts, block_number, market_infos).await
+}
+
+// Splits sorted list indices into ranges of consecutive indices, e.g. [0, 1, 2, 5, 6] -> [0..3, 5..7]
+fn consecutive_ranges(indices: &[usize]) -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+    for &index in indices {
+        match ranges.last_mut() {
+            Some(range) if range.end == index => range.end += 1,
+            _ => ranges.push(index..index + 1),
+        }
+    }
+    ranges
+}
+
+// getMarketInfo leaves out pool amounts and open interest. Those are read from the DataStore at the same block,
+// for every market in one Multicall3 batch: the long and short pool amounts, then the long and short open
+// interest for each collateral token.
+async fn build_market_snapshots(
+    contracts: &Contracts,
+    block_number: U64,
+    market_infos: Vec<(Markets, ReaderMarketInfo)>,
+) -> Result<Vec<MarketSnapshot>, Box<dyn std::error::Error>> {
+    let keys: KeyHashes = KeyHashes::new();
+    let mut multicall = Multicall::new_with_chain_id(contracts.data_store_contract.client(), None, Some(ARBITRUM_CHAIN_ID))?
+        .block(block_number);
+
+    for (_, market_info) in &market_infos {
+        let market_props: &MarketProps = &market_info.market;
+        for pool_token in [market_props.long_token, market_props.short_token] {
+            let key: [u8; 32] = scoped_key(&keys.pool_amount, vec!["address", "address"],
+                vec![AbiToken::Address(market_props.market_token), AbiToken::Address(pool_token)])?;
+            multicall.add_call(contracts.data_store_contract.get_uint(key), false);
+        }
+        for collateral_token in collateral_tokens(market_props) {
+            for is_long in [true, false] {
+                let key: [u8; 32] = scoped_key(&keys.open_interest, vec!["address", "address", "bool"],
+                    vec![AbiToken::Address(market_props.market_token), AbiToken::Address(collateral_token), AbiToken::Bool(is_long)])?;
+                multicall.add_call(contracts.data_store_contract.get_uint(key), false);
+            }
+        }
+    }
+
+    let values: Vec<U256> = multicall.call_array().await?;
+    let mut values = values.into_iter();
+    let mut next_value = || values.next().ok_or("Multicall returned fewer values than requested");
+
+    let mut snapshots: Vec<MarketSnapshot> = Vec::new();
+    for (market, market_info) in market_infos {
+        let market_props: &MarketProps = &market_info.market;
+        let long_pool_amount: U256 = next_value()?;
+        let short_pool_amount: U256 = next_value()?;
+
+        // Open interest is tracked per collateral token, so each side is the sum over the long and short tokens
+        let mut long_open_interest_usd: U256 = U256::zero();
+        let mut short_open_interest_usd: U256 = U256::zero();
+        for _ in collateral_tokens(market_props) {
+            long_open_interest_usd += next_value()?;
+            short_open_interest_usd += next_value()?;
+        }
+
+        snapshots.push(MarketSnapshot {
+            market,
+            market_token: market_props.market_token,
+            index_token: market_props.index_token,
+            long_token: market_props.long_token,
+            short_token: market_props.short_token,
+            long_pool_amount,
+            short_pool_amount,
+            long_open_interest_usd,
+            short_open_interest_usd,
+            borrowing_factor_per_second_for_longs: market_info.borrowing_factor_per_second_for_longs,
+            borrowing_factor_per_second_for_shorts: market_info.borrowing_factor_per_second_for_shorts,
+            longs_pay_shorts: market_info.next_funding.longs_pay_shorts,
+            funding_factor_per_second: market_info.next_funding.funding_factor_per_second,
+            next_saved_funding_factor_per_second: market_info.next_funding.next_saved_funding_factor_per_second,
+            virtual_pool_amount_for_long_token: market_info.virtual_inventory.virtual_pool_amount_for_long_token,
+            virtual_pool_amount_for_short_token: market_info.virtual_inventory.virtual_pool_amount_for_short_token,
+            virtual_inventory_for_positions: market_info.virtual_inventory.virtual_inventory_for_positions,
+            is_disabled: market_info.is_disabled,
+        });
+    }
+
+    Ok(snapshots)
+}
+
+// Single token markets use the same token on both sides, its open interest is only stored once
+fn collateral_tokens(market_props: &MarketProps) -> Vec<Address> {
+    let mut collateral_tokens: Vec<Address> = vec![market_props.long_token];
+    if market_props.short_token != market_props.long_token {
+        collateral_tokens.push(market_props.short_token);
+    }
+    collateral_tokens
+}
+
+fn scoped_key(base_key_hex: &str, data_types: Vec<&str>, data_values: Vec<AbiToken>) -> Result<[u8; 32], Box<dyn std::error::Error>> {
+    let key: H256 = H256::from_slice(&hex::decode(hash_scoped_key(base_key_hex, data_types, data_values))?);
+
+    Ok(key.to_fixed_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listed_markets_are_read_in_consecutive_ranges() {
+        assert_eq!(consecutive_ranges(&[0, 1, 2, 5, 6, 9]), vec![0..3, 5..7, 9..10]);
+        assert_eq!(consecutive_ranges(&[4]), vec![4..5]);
+        assert!(consecutive_ranges(&[]).is_empty());
+    }
+}
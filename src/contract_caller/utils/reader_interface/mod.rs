@@ -1,2 +1,3 @@
 pub mod reader_functions;
+pub mod market_functions;
 pub mod api_caller;
\ No newline at end of file
@@ -1,6 +1,17 @@
 use ethers::abi::Token;
-use ethers::types::{Address, H160, H256};
+use ethers::prelude::{Http, Middleware, Provider};
+use ethers::types::{Address, H160, H256, U256, U64};
+use std::collections::hash_map::Entry;
+use std::collections::HashMap;
+use std::sync::Arc;
+use crate::contract_caller::connect_provider::connect_provider;
+use crate::contract_caller::order_builder::get_price::fetch_token_prices_by_address;
+use crate::contract_caller::utils::contract_addresses::{Contracts, MarketPrices, MarketProps, OrderProps, PositionInfo as ReaderPositionInfo, PositionProps, PriceProps};
 use crate::contract_caller::utils::hash_utils::hash_data;
+use crate::contract_caller::utils::structs::{KeyHashes, Order, Position, PositionInfo, TokenPriceFromApiResponse};
+
+// Referral discounts and rebates are part of the position fees, so the Reader needs the ReferralStorage to price them
+pub const REFERRAL_STORAGE_ADDRESS: &str = "0xe6fab3F0c7199b0d34d7FbE83394fc0e0D06e99d";
 
 pub fn get_position_key(account: H160, market: H160, collateral_token: H160, is_long: bool) -> H256 {
     let data_values = vec![
@@ -16,3 +27,434 @@ pub fn get_position_key(account: H160, market: H160, collateral_token: H160, is_
     H256::from_slice(&hex::decode(hash_hex).expect("Invalid hex string"))
 }
 
+// Returns None when nothing is open under the key, the Reader hands back an all-zero position in that case
+pub async fn get_position(position_key: H256) -> Result<Option<Position>, Box<dyn std::error::Error>> {
+    let provider: Provider<Http> = connect_provider().await?;
+    let contracts: Contracts = Contracts::new(Arc::new(provider));
+    let data_store: Address = contracts.data_store_contract.address();
+
+    let position: PositionProps = contracts.reader_contract
+        .get_position(data_store, position_key.to_fixed_bytes())
+        .call()
+        .await?;
+
+    if position.addresses.account.is_zero() {
+        return Ok(None);
+    }
+
+    Ok(Some(Position::from(position)))
+}
+
+pub async fn get_account_positions(account: H160) -> Result<Vec<Position>, Box<dyn std::error::Error>> {
+    let provider: Provider<Http> = connect_provider().await?;
+    let block_number: U64 = provider.get_block_number().await?;
+    let contracts: Contracts = Contracts::new(Arc::new(provider));
+
+    let positions: Vec<PositionProps> = read_account_positions(&contracts, account, block_number).await?;
+
+    Ok(positions.into_iter().map(Position::from).collect())
+}
+
+// The position count and the positions are read at the same block, so the range always matches the list
+async fn read_account_positions(contracts: &Contracts, account: H160, block_number: U64) -> Result<Vec<PositionProps>, Box<dyn std::error::Error>> {
+    let data_store: Address = contracts.data_store_contract.address();
+
+    let position_count: U256 = contracts.data_store_contract
+        .get_bytes_32_count(get_account_position_list_key(account).to_fixed_bytes())
+        .block(block_number)
+        .call()
+        .await?;
+    let positions: Vec<PositionProps> = contracts.reader_contract
+        .get_account_positions(data_store, account, U256::zero(), position_count)
+        .block(block_number)
+        .call()
+        .await?;
+
+    Ok(positions)
+}
+
+// Values a full close of the position at the current oracle prices: PnL, price impact, pending fees and net value
+pub async fn get_position_info(position_key: H256) -> Result<PositionInfo, Box<dyn std::error::Error>> {
+    let provider: Provider<Http> = connect_provider().await?;
+    let block_number: U64 = provider.get_block_number().await?;
+    let contracts: Contracts = Contracts::new(Arc::new(provider));
+    let data_store: Address = contracts.data_store_contract.address();
+    let referral_storage: Address = REFERRAL_STORAGE_ADDRESS.parse().expect("Invalid H160 address");
+
+    let position: PositionProps = contracts.reader_contract
+        .get_position(data_store, position_key.to_fixed_bytes())
+        .block(block_number)
+        .call()
+        .await?;
+    if position.addresses.account.is_zero() {
+        return Err("No open position found for this key".into());
+    }
+
+    let token_prices: HashMap<Address, TokenPriceFromApiResponse> = fetch_token_prices_by_address().await?;
+    let market_props: MarketProps = contracts.reader_contract
+        .get_market(data_store, position.addresses.market)
+        .block(block_number)
+        .call()
+        .await?;
+    let prices: MarketPrices = get_market_prices(&market_props, &token_prices)?;
+
+    let position_info: ReaderPositionInfo = contracts.reader_contract
+        .get_position_info(data_store, referral_storage, position_key.to_fixed_bytes(), prices, U256::zero(), Address::zero(), true)
+        .block(block_number)
+        .call()
+        .await?;
+
+    Ok(PositionInfo::from(position_info))
+}
+
+// Same as get_position_info for every open position of the account, in one Reader call. Prices are
+// downloaded once and every read is pinned to one block.
+pub async fn get_account_position_info_list(account: H160) -> Result<Vec<PositionInfo>, Box<dyn std::error::Error>> {
+    let provider: Provider<Http> = connect_provider().await?;
+    let block_number: U64 = provider.get_block_number().await?;
+    let contracts: Contracts = Contracts::new(Arc::new(provider));
+    let data_store: Address = contracts.data_store_contract.address();
+    let referral_storage: Address = REFERRAL_STORAGE_ADDRESS.parse().expect("Invalid H160 address");
+
+    let positions: Vec<PositionProps> = read_account_positions(&contracts, account, block_number).await?;
+    if positions.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let token_prices: HashMap<Address, TokenPriceFromApiResponse> = fetch_token_prices_by_address().await?;
+    let mut prices_by_market: HashMap<Address, MarketPrices> = HashMap::new();
+    let mut position_keys: Vec<[u8; 32]> = Vec::new();
+    let mut prices: Vec<MarketPrices> = Vec::new();
+    for position in positions {
+        let market: Address = position.addresses.market;
+        if let Entry::Vacant(entry) = prices_by_market.entry(market) {
+            let market_props: MarketProps = contracts.reader_contract
+                .get_market(data_store, market)
+                .block(block_number)
+                .call()
+                .await?;
+            entry.insert(get_market_prices(&market_props, &token_prices)?);
+        }
+
+        position_keys.push(Position::from(position).key.to_fixed_bytes());
+        prices.push(prices_by_market[&market].clone());
+    }
+
+    let position_info_list: Vec<ReaderPositionInfo> = contracts.reader_contract
+        .get_account_position_info_list(data_store, referral_storage, position_keys, prices, Address::zero())
+        .block(block_number)
+        .call()
+        .await?;
+
+    Ok(position_info_list.into_iter().map(PositionInfo::from).collect())
+}
+
+// Oracle min and max prices for the market's index, long and short tokens, already in contract precision.
+// Swap-only markets have no index token, so they are priced with their long token instead.
+pub fn get_market_prices(
+    market_props: &MarketProps,
+    token_prices: &HashMap<Address, TokenPriceFromApiResponse>,
+) -> Result<MarketPrices, Box<dyn std::error::Error>> {
+    let index_token: Address = if market_props.index_token.is_zero() {
+        market_props.long_token
+    } else {
+        market_props.index_token
+    };
+
+    Ok(MarketPrices {
+        index_token_price: get_price_props(index_token, token_prices)?,
+        long_token_price: get_price_props(market_props.long_token, token_prices)?,
+        short_token_price: get_price_props(market_props.short_token, token_prices)?,
+    })
+}
+
+// A missing or zero price would make the Reader value the market as empty, so it is an error rather than a default
+fn get_price_props(token: Address, token_prices: &HashMap<Address, TokenPriceFromApiResponse>) -> Result<PriceProps, Box<dyn std::error::Error>> {
+    let price: &TokenPriceFromApiResponse = token_prices.get(&token)
+        .ok_or(format!("No oracle price for token {:?}", token))?;
+
+    let price_props: PriceProps = PriceProps {
+        min: U256::from_dec_str(&price.min_price_full)
+            .map_err(|e| format!("Error parsing {} min price: {}", price.token_symbol, e))?,
+        max: U256::from_dec_str(&price.max_price_full)
+            .map_err(|e| format!("Error parsing {} max price: {}", price.token_symbol, e))?,
+    };
+    if price_props.min.is_zero() || price_props.max.is_zero() {
+        return Err(format!("Oracle price for {} is zero", price.token_symbol).into());
+    }
+
+    Ok(price_props)
+}
+
+// DataStore keeps each account's open order keys in a bytes32 set under keccak(ACCOUNT_ORDER_LIST, account)
+pub fn get_account_order_list_key(account: H160) -> H256 {
+    let keys: KeyHashes = KeyHashes::new();
+    get_account_list_key(&keys.account_order_list, account)
+}
+
+// Same layout as the order list, under ACCOUNT_POSITION_LIST
+pub fn get_account_position_list_key(account: H160) -> H256 {
+    let keys: KeyHashes = KeyHashes::new();
+    get_account_list_key(&keys.account_position_list, account)
+}
+
+fn get_account_list_key(list_key_hex: &str, account: H160) -> H256 {
+    let list_key: H256 = H256::from_slice(&hex::decode(list_key_hex).expect("Invalid hex string"));
+    let data_values = vec![
+        Token::FixedBytes(list_key.as_bytes().to_vec()),
+        Token::Address(account),
+    ];
+
+    let hash_hex = hash_data(vec!["bytes32", "address"], data_values);
+
+    H256::from_slice(&hex::decode(hash_hex).expect("Invalid hex string"))
+}
+
+// Returns None when no order is stored under the key, the Reader hands back an all-zero order in that case
+pub async fn get_order(order_key: H256) -> Result<Option<Order>, Box<dyn std::error::Error>> {
+    let provider: Provider<Http> = connect_provider().await?;
+    let contracts: Contracts = Contracts::new(Arc::new(provider));
+    let data_store: Address = contracts.data_store_contract.address();
+
+    let order: OrderProps = contracts.reader_contract
+        .get_order(data_store, order_key.to_fixed_bytes())
+        .call()
+        .await?;
+
+    if order.addresses.account.is_zero() {
+        return Ok(None);
+    }
+
+    Ok(Some(Order::from_props(order_key, order)?))
+}
+
+// Every resting order of the account. The keys and the orders are read at the same block,
+// so they line up even if orders are created or executed in between the two calls.
+// Orders whose type or swap type cannot be decoded are logged and skipped, so one of them does not hide the rest.
+pub async fn get_account_orders(account: H160) -> Result<Vec<Order>, Box<dyn std::error::Error>> {
+    let provider: Provider<Http> = connect_provider().await?;
+    let block_number: U64 = provider.get_block_number().await?;
+    let contracts: Contracts = Contracts::new(Arc::new(provider));
+    let data_store: Address = contracts.data_store_contract.address();
+    let list_key: [u8; 32] = get_account_order_list_key(account).to_fixed_bytes();
+
+    let order_count: U256 = contracts.data_store_contract
+        .get_bytes_32_count(list_key)
+        .block(block_number)
+        .call()
+        .await?;
+    let order_keys: Vec<[u8; 32]> = contracts.data_store_contract
+        .get_bytes_32_values_at(list_key, U256::zero(), order_count)
+        .block(block_number)
+        .call()
+        .await?;
+    let orders: Vec<OrderProps> = contracts.reader_contract
+        .get_account_orders(data_store, account, U256::zero(), order_count)
+        .block(block_number)
+        .call()
+        .await?;
+
+    decode_account_orders(order_keys, orders)
+}
+
+// Pairs each order with the key it is stored under, skipping any order that cannot be decoded
+fn decode_account_orders(order_keys: Vec<[u8; 32]>, orders: Vec<OrderProps>) -> Result<Vec<Order>, Box<dyn std::error::Error>> {
+    if orders.len() != order_keys.len() {
+        return Err("Order keys and orders returned by the Reader do not line up".into());
+    }
+
+    let mut account_orders: Vec<Order> = Vec::new();
+    for (order_key, order) in order_keys.into_iter().zip(orders) {
+        let order_key: H256 = H256::from(order_key);
+        match Order::from_props(order_key, order) {
+            Ok(order) => account_orders.push(order),
+            Err(e) => println!("Skipping order {:?}: {}", order_key, e),
+        }
+    }
+
+    Ok(account_orders)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::contract_caller::utils::contract_addresses::{GetPositionInfoReturn, OrderNumbers};
+    use crate::contract_caller::utils::structs::OrderType;
+    use ethers::abi::AbiDecode;
+    use ethers::types::I256;
+
+    const WETH: &str = "0x82aF49447D8a07e3bd95BD0d56f35241523fBab1";
+    const USDC: &str = "0xaf88d065e77c8cC2239327C5EDb3A432268e5831";
+
+    // getPositionInfo return data for a $2000 ETH long opened at $2500 with 1000 USDC collateral, valued at $2600:
+    // $80 base PnL, -$0.50 close price impact, 0.12 USDC funding, $0.30 borrowing and 1.40014 USDC position fee
+    const ETH_LONG_POSITION_INFO: &str = concat!(
+        "000000000000000000000000729fbbb8a11cf2d564ba5fd913aedef1d9a6ea66",
+        "00000000000000000000000070d95587d40a2caf56bd97485ab3eec10bee6336",
+        "000000000000000000000000af88d065e77c8cc2239327c5edb3a432268e5831",
+        "000000000000000000000000000000000000629b8c891b267182b61400000000",
+        "0000000000000000000000000000000000000000000000000b1a2bc2ec500000",
+        "000000000000000000000000000000000000000000000000000000003b9aca00",
+        "000000000000000000000000000000000000000cc710b8028d33b79bae398115",
+        "00000000000000000000000000000000000000000000000000128277a747a000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "000000000000000000000000000000000000000000000000000000000ee6b280",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000001",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "000000000000000000000000000000000000000000000000000000000001d4c0",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "00000000000000000000000000000000000000000000000000130ae43fff0000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000003c95a2f0b4856475fe0000000",
+        "00000000000000000000000000000000000000000000000000000000000493fe",
+        "0000000000000000000000000000000000000007f3a3c9314b1b95e2f0000000",
+        "000000000000000000000000000000000000000000000000000000000002e25b",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "00000000000000000000000000000000000000000000d3bcb0076ec03df00000",
+        "00000000000000000000000000000000000000000000d3c21bcecceda1000000",
+        "0000000000000000000000000000000000000000024306c4097859c43c000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "0000000000000000000000000000000000000004ab88d39efb59580750000000",
+        "000000000000000000000000000000000000000000000000000000000007e7a4",
+        "00000000000000000000000000000000000000000000000000000000000d75a8",
+        "00000000000000000000000000000000000000000000000000000000000d75a8",
+        "0000000000000000000000000000000000000000000000000000000000155d4c",
+        "000000000000000000000000000000000000000000000000000000000019f14a",
+        "00000000000000000000000000000000000000000000000000000000001bc60a",
+        "fffffffffffffffffffffffffffffffffffffff9b069b197dcc5890ae0000000",
+        "0000000000000000000000000000000000000000000000000000000000000000",
+        "00000000000000000000000000000000000000000000000000093ccb9cf93000",
+        "00000000000000000000000000000000000003f1bdf10116048a593400000000",
+        "00000000000000000000000000000000000003f1bdf10116048a593400000000",
+        "00000000000000000000000000000000000003eb6e5ab2ade14fe23ee0000000",
+    );
+
+    fn address(address: &str) -> Address {
+        address.parse().expect("Invalid H160 address")
+    }
+
+    fn token_prices() -> HashMap<Address, TokenPriceFromApiResponse> {
+        HashMap::from([
+            (address(WETH), TokenPriceFromApiResponse {
+                token_symbol: "ETH".to_string(),
+                min_price_full: "3000000000000000".to_string(),
+                max_price_full: "3001000000000000".to_string(),
+            }),
+            (address(USDC), TokenPriceFromApiResponse {
+                token_symbol: "USDC".to_string(),
+                min_price_full: "999900000000000000000000".to_string(),
+                max_price_full: "1000000000000000000000000".to_string(),
+            }),
+        ])
+    }
+
+    fn market(index_token: Address) -> MarketProps {
+        MarketProps {
+            market_token: address("0x70d95587d40A2caf56bd97485aB3Eec10Bee6336"),
+            index_token,
+            long_token: address(WETH),
+            short_token: address(USDC),
+        }
+    }
+
+    #[test]
+    fn prices_index_long_and_short_tokens() {
+        let prices: MarketPrices = get_market_prices(&market(address(WETH)), &token_prices()).expect("Failed to price market");
+
+        assert_eq!(prices.index_token_price.min, U256::from(3_000_000_000_000_000u64));
+        assert_eq!(prices.long_token_price.max, U256::from(3_001_000_000_000_000u64));
+        assert_eq!(prices.short_token_price.min, U256::from_dec_str("999900000000000000000000").unwrap());
+    }
+
+    #[test]
+    fn swap_only_market_is_priced_with_its_long_token() {
+        let prices: MarketPrices = get_market_prices(&market(Address::zero()), &token_prices()).expect("Failed to price market");
+
+        assert_eq!(prices.index_token_price, prices.long_token_price);
+    }
+
+    #[test]
+    fn missing_or_zero_price_is_an_error() {
+        let mut without_usdc: HashMap<Address, TokenPriceFromApiResponse> = token_prices();
+        without_usdc.remove(&address(USDC));
+        assert!(get_market_prices(&market(address(WETH)), &without_usdc).is_err());
+
+        let mut zero_eth: HashMap<Address, TokenPriceFromApiResponse> = token_prices();
+        zero_eth.get_mut(&address(WETH)).unwrap().min_price_full = "0".to_string();
+        assert!(get_market_prices(&market(address(WETH)), &zero_eth).is_err());
+    }
+
+    #[test]
+    fn position_info_decodes_from_reader_return_data() {
+        let return_data: Vec<u8> = hex::decode(ETH_LONG_POSITION_INFO).expect("Invalid hex string");
+        let GetPositionInfoReturn(reader_info) = GetPositionInfoReturn::decode(return_data).expect("Failed to decode getPositionInfo");
+        let info: PositionInfo = PositionInfo::from(reader_info);
+
+        let collateral_token_price: U256 = U256::from_dec_str("999900000000000000000000").unwrap();
+        let pnl_after_price_impact_usd: I256 = I256::from(795) * I256::exp10(29);
+
+        assert_eq!(info.position.account, address("0x729fBbB8a11Cf2d564Ba5Fd913AEdEf1D9a6ea66"));
+        assert_eq!(info.position.collateral_token, address(USDC));
+        assert!(info.position.is_long);
+        assert_eq!(info.position.size_in_usd, U256::from(2000u64) * U256::exp10(30));
+        assert_eq!(info.position.size_in_tokens, U256::exp10(17) * 8);
+        assert_eq!(info.collateral_token_price, collateral_token_price);
+        assert_eq!(info.collateral_usd, U256::from(1_000_000_000u64) * collateral_token_price);
+        assert_eq!(info.base_pnl_usd, I256::from(80) * I256::exp10(30));
+        assert_eq!(info.price_impact_usd, -I256::from(5) * I256::exp10(29));
+        assert_eq!(info.pnl_after_price_impact_usd, pnl_after_price_impact_usd);
+        assert_eq!(info.execution_price, U256::from(2_600_120_000_000_000u64));
+
+        assert_eq!(info.fees.funding_fee_usd, U256::from(120_000u64) * collateral_token_price);
+        assert_eq!(info.fees.borrowing_fee_usd, U256::from(3u64) * U256::exp10(29));
+        assert_eq!(info.fees.position_fee_amount, U256::from(1_400_140u64));
+        assert_eq!(info.fees.total_cost_amount, U256::from(1_820_170u64));
+        assert_eq!(
+            info.net_value_usd,
+            I256::from_raw(info.collateral_usd) + pnl_after_price_impact_usd - I256::from_raw(U256::from(1_820_170u64) * collateral_token_price),
+        );
+    }
+
+    fn order_of_type(order_type: u8) -> OrderProps {
+        OrderProps {
+            numbers: OrderNumbers { order_type, ..Default::default() },
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn undecodable_orders_are_skipped() {
+        let order_keys: Vec<[u8; 32]> = vec![[1; 32], [2; 32], [3; 32]];
+        // A Liquidation order can be listed for the account but cannot be built into an Order
+        let orders: Vec<OrderProps> = vec![order_of_type(3), order_of_type(7), order_of_type(5)];
+
+        let decoded: Vec<Order> = decode_account_orders(order_keys, orders).expect("Failed to decode orders");
+
+        assert_eq!(decoded.len(), 2);
+        assert_eq!(decoded[0].key, H256::from([1; 32]));
+        assert_eq!(decoded[0].order_type, OrderType::LimitIncrease);
+        assert_eq!(decoded[1].key, H256::from([3; 32]));
+        assert_eq!(decoded[1].order_type, OrderType::LimitDecrease);
+    }
+
+    #[test]
+    fn mismatched_keys_and_orders_are_an_error() {
+        let order_keys: Vec<[u8; 32]> = vec![[1; 32], [2; 32]];
+        let orders: Vec<OrderProps> = vec![order_of_type(3)];
+
+        assert!(decode_account_orders(order_keys, orders).is_err());
+    }
+}
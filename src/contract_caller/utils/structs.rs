@@ -1,7 +1,10 @@
 use ethers::prelude::*;
-use serde::{Deserialize};
+use ethers::types::transaction::eip2718::TypedTransaction;
+use serde::{Deserialize, Serialize};
 
+use super::contract_addresses::{CreateOrderCall, OrderProps, PositionInfo as ReaderPositionInfo, PositionProps, SendTokensCall, SendWntCall};
 use super::hash_utils::hash_string;
+use super::reader_interface::reader_functions::get_position_key;
 
 // Struct for 'addresses' parameter in 'createOrder'
 pub struct CreateOrderParamsAddresses {
@@ -25,11 +28,14 @@ pub struct CreateOrderParamsNumbers {
 }
 
 pub struct MarketInfo {
-    market: String,
-    market_address: String,
-    is_synthetic: Option<bool>,
+    pub market: String,
+    pub market_address: String,
+    pub is_synthetic: Option<bool>,
+    pub long_token: String,
+    pub short_token: String,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Markets {
     ETH,
     WBTC,
@@ -52,61 +58,85 @@ impl Markets {
                 market: "ETH".to_string(),
                 market_address: "0x70d95587d40A2caf56bd97485aB3Eec10Bee6336".to_string(),
                 is_synthetic: Some(false),
+                long_token: "ETH".to_string(),
+                short_token: "USDC".to_string(),
             },
             Markets::WBTC => MarketInfo {
                 market: "WBTC".to_string(),
                 market_address: "0x47c031236e19d024b42f8AE6780E44A573170703".to_string(),
                 is_synthetic: Some(false),
+                long_token: "WBTC".to_string(),
+                short_token: "USDC".to_string(),
             },
             Markets::DOGE => MarketInfo {
                 market: "DOGE".to_string(),
                 market_address: "0x6853EA96FF216fAb11D2d930CE3C508556A4bdc4".to_string(),
                 is_synthetic: Some(true),
+                long_token: "ETH".to_string(),
+                short_token: "USDC".to_string(),
             },
             Markets::SOL => MarketInfo {
                 market: "SOL".to_string(),
                 market_address: "0x09400D9DB990D5ed3f35D7be61DfAEB900Af03C9".to_string(),
                 is_synthetic: Some(false),
+                long_token: "SOL".to_string(),
+                short_token: "USDC".to_string(),
             },
             Markets::LTC => MarketInfo {
                 market: "LTC".to_string(),
                 market_address: "0xD9535bB5f58A1a75032416F2dFe7880C30575a41".to_string(),
                 is_synthetic: Some(true),
+                long_token: "ETH".to_string(),
+                short_token: "USDC".to_string(),
             },
             Markets::UNI => MarketInfo {
                 market: "UNI".to_string(),
                 market_address: "0xc7Abb2C5f3BF3CEB389dF0Eecd6120D451170B50".to_string(),
                 is_synthetic: Some(false),
+                long_token: "UNI".to_string(),
+                short_token: "USDC".to_string(),
             },
             Markets::LINK => MarketInfo {
                 market: "LINK".to_string(),
                 market_address: "0x7f1fa204bb700853D36994DA19F830b6Ad18455C".to_string(),
                 is_synthetic: Some(false),
+                long_token: "LINK".to_string(),
+                short_token: "USDC".to_string(),
             },
             Markets::ARB => MarketInfo {
                 market: "ARB".to_string(),
                 market_address: "0xC25cEf6061Cf5dE5eb761b50E4743c1F5D7E5407".to_string(),
                 is_synthetic: Some(false),
+                long_token: "ARB".to_string(),
+                short_token: "USDC".to_string(),
             },
             Markets::USDCE => MarketInfo {
                 market: "USDCE".to_string(),
                 market_address: "0x9C2433dFD71096C435Be9465220BB2B189375eA7".to_string(),
                 is_synthetic: Some(false),
+                long_token: "USDC".to_string(),
+                short_token: "USDCE".to_string(),
             },
             Markets::USDT => MarketInfo {
                 market: "USDT".to_string(),
                 market_address: "0xB686BcB112660343E6d15BDb65297e110C8311c4".to_string(),
                 is_synthetic: Some(false),
+                long_token: "USDC".to_string(),
+                short_token: "USDT".to_string(),
             },
             Markets::DAI => MarketInfo {
                 market: "DAI".to_string(),
                 market_address: "0xe2fEDb9e6139a182B98e7C2688ccFa3e9A53c665".to_string(),
                 is_synthetic: Some(false),
+                long_token: "USDC".to_string(),
+                short_token: "DAI".to_string(),
             },
             Markets::XRP => MarketInfo {
                 market: "XRP".to_string(),
                 market_address: "0x0CCB4fAa6f1F1B30911619f1184082aB4E25813c".to_string(),
                 is_synthetic: Some(true),
+                long_token: "ETH".to_string(),
+                short_token: "USDC".to_string(),
             },
         }
     }
@@ -129,11 +159,79 @@ impl Markets {
         }
     }
 
+    pub fn from_address(address: Address) -> Option<Self> {
+        Markets::all()
+            .into_iter()
+            .find(|market| market.info().market_address.parse::<Address>().ok() == Some(address))
+    }
+
+    // Follows a swap path through each pool, returning the token that comes out the other end
+    pub fn get_swap_output_token(from_token: &str, swap_path: &[Markets]) -> Option<String> {
+        let mut current_token: String = from_token.to_string();
+        for market in swap_path {
+            let info: MarketInfo = market.info();
+            current_token = if current_token == info.long_token {
+                info.short_token
+            } else if current_token == info.short_token {
+                info.long_token
+            } else {
+                return None;
+            };
+        }
+
+        Some(current_token)
+    }
+
     pub fn get_market_address(token_name: &str) -> Option<String> {
         Markets::from_token_name(token_name)
             .map(|market| market.info().market_address)
     }
 
+    pub fn all() -> Vec<Self> {
+        vec![
+            Markets::ETH,
+            Markets::WBTC,
+            Markets::DOGE,
+            Markets::SOL,
+            Markets::LTC,
+            Markets::UNI,
+            Markets::LINK,
+            Markets::ARB,
+            Markets::USDCE,
+            Markets::USDT,
+            Markets::DAI,
+            Markets::XRP,
+        ]
+    }
+
+    // Every token is pooled against USDC, so the first market holding both is the one to swap through
+    pub fn get_swap_market_for_token(token_name: &str) -> Option<String> {
+        Markets::all()
+            .into_iter()
+            .map(|market| market.info())
+            .find(|info| {
+                (info.long_token == token_name && info.short_token == "USDC")
+                    || (info.long_token == "USDC" && info.short_token == token_name)
+            })
+            .map(|info| info.market_address)
+    }
+
+    pub fn get_swap_path(from_token: &str, to_token: &str) -> Option<Vec<String>> {
+        if from_token == to_token {
+            return None;
+        }
+
+        let mut swap_path: Vec<String> = Vec::new();
+        if from_token != "USDC" {
+            swap_path.push(Markets::get_swap_market_for_token(from_token)?);
+        }
+        if to_token != "USDC" {
+            swap_path.push(Markets::get_swap_market_for_token(to_token)?);
+        }
+
+        Some(swap_path)
+    }
+
     pub fn get_swap_path_for_collateral(collateral_token: &str) -> Vec<String> {
         if collateral_token != "USDC" {
             if let Some(market_address) = Markets::get_market_address(collateral_token) {
@@ -147,8 +245,34 @@ impl Markets {
     }
 }
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct OrderObject {
+    pub is_long: bool,
+    pub position_asset: String,
+    pub amount: U256,
+    pub receiver: Address,
+    pub callback_contract: Address,
+    pub ui_fee_receiver: Address,
+    pub market: Address,
+    pub initial_collateral_token: Address,
+    pub swap_path: Vec<Address>,
+    pub size_delta_usd: U256,
+    pub initial_collateral_delta_amount: U256,
+    pub trigger_price: U256,
+    pub acceptable_price: U256,
+    pub execution_fee: U256,
+    pub callback_gas_limit: U256,
+    pub min_output_amount: U256,
+    pub order_type: OrderType,
+    pub decrease_position_swap_type: DecreasePositionSwapType,
+    pub should_unwrap_native_token: bool,
+    pub referral_code: H256,
+}
+
+// String form of OrderObject for config files and JSON. Numbers are decimal strings, addresses are hex,
+// and initial_collateral_token may be either a token name ("ETH") or an address.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct RawOrderObject {
     pub is_long: bool,
     pub position_asset: String,
     pub amount: String,
@@ -165,32 +289,98 @@ pub struct OrderObject {
     pub execution_fee: String,
     pub callback_gas_limit: String,
     pub min_output_amount: String,
-    pub order_type: u8,
-    pub decrease_position_swap_type: u8,
+    pub order_type: OrderType,
+    pub decrease_position_swap_type: DecreasePositionSwapType,
     pub should_unwrap_native_token: bool,
     pub referral_code: String,
 }
 
+// Mirrors Order.OrderType in the GMX contracts. Liquidation (7) is deliberately absent:
+// only keepers can create liquidation orders, so there is no way to build one here.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum OrderType {
-    MarketSwap,
-    LimitSwap,
-    MarketIncrease,
-    LimitIncrease,
-    MarketDecrease,
-    LimitDecrease,
-    StopLossDecrease,
-    Liquidation,
+    MarketSwap = 0,
+    LimitSwap = 1,
+    MarketIncrease = 2,
+    LimitIncrease = 3,
+    MarketDecrease = 4,
+    LimitDecrease = 5,
+    StopLossDecrease = 6,
+}
+
+impl OrderType {
+    pub fn is_swap(&self) -> bool {
+        matches!(self, OrderType::MarketSwap | OrderType::LimitSwap)
+    }
+
+    pub fn is_increase(&self) -> bool {
+        matches!(self, OrderType::MarketIncrease | OrderType::LimitIncrease)
+    }
+
+    pub fn is_decrease(&self) -> bool {
+        matches!(self, OrderType::MarketDecrease | OrderType::LimitDecrease | OrderType::StopLossDecrease)
+    }
+
+    // Executed by a keeper straight away instead of resting until a trigger price is reached
+    pub fn is_market(&self) -> bool {
+        matches!(self, OrderType::MarketSwap | OrderType::MarketIncrease | OrderType::MarketDecrease)
+    }
 }
 
+impl From<OrderType> for u8 {
+    fn from(order_type: OrderType) -> u8 {
+        order_type as u8
+    }
+}
+
+impl TryFrom<u8> for OrderType {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(OrderType::MarketSwap),
+            1 => Ok(OrderType::LimitSwap),
+            2 => Ok(OrderType::MarketIncrease),
+            3 => Ok(OrderType::LimitIncrease),
+            4 => Ok(OrderType::MarketDecrease),
+            5 => Ok(OrderType::LimitDecrease),
+            6 => Ok(OrderType::StopLossDecrease),
+            7 => Err("Liquidation orders can only be created by keepers".to_string()),
+            _ => Err(format!("Unknown order type: {}", value)),
+        }
+    }
+}
+
+// Mirrors Order.DecreasePositionSwapType in the GMX contracts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum DecreasePositionSwapType {
-    NoSwap,
-    SwapPnlTokenToCollateralToken,
-    SwapCollateralTokenToPnlToken
+    NoSwap = 0,
+    SwapPnlTokenToCollateralToken = 1,
+    SwapCollateralTokenToPnlToken = 2,
 }
 
-impl Default for OrderObject {
+impl From<DecreasePositionSwapType> for u8 {
+    fn from(swap_type: DecreasePositionSwapType) -> u8 {
+        swap_type as u8
+    }
+}
+
+impl TryFrom<u8> for DecreasePositionSwapType {
+    type Error = String;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(DecreasePositionSwapType::NoSwap),
+            1 => Ok(DecreasePositionSwapType::SwapPnlTokenToCollateralToken),
+            2 => Ok(DecreasePositionSwapType::SwapCollateralTokenToPnlToken),
+            _ => Err(format!("Unknown decrease position swap type: {}", value)),
+        }
+    }
+}
+
+impl Default for RawOrderObject {
     fn default() -> Self {
-        OrderObject {
+        RawOrderObject {
             is_long: true,
             position_asset: "".to_string(),
             amount: "".to_string(),
@@ -207,20 +397,205 @@ impl Default for OrderObject {
             execution_fee: "".to_string(),
             callback_gas_limit: "".to_string(),
             min_output_amount: "".to_string(),
-            order_type: 0,
-            decrease_position_swap_type: 0,
+            order_type: OrderType::MarketSwap,
+            decrease_position_swap_type: DecreasePositionSwapType::NoSwap,
             should_unwrap_native_token: false,
             referral_code: "0x0000000000000000000000000000000000000000000000000000000000000000".to_string(),
         }
     }
 }
 
+impl TryFrom<RawOrderObject> for OrderObject {
+    type Error = Box<dyn std::error::Error>;
+
+    fn try_from(raw: RawOrderObject) -> Result<Self, Self::Error> {
+        let parse_u256 = |name: &str, value: &str| -> Result<U256, Box<dyn std::error::Error>> {
+            U256::from_dec_str(value).map_err(|e| format!("Error parsing {} to U256: {}", name, e).into())
+        };
+        let parse_address = |name: &str, value: &str| -> Result<Address, Box<dyn std::error::Error>> {
+            value.parse::<Address>().map_err(|e| format!("Error parsing {} address: {}", name, e).into())
+        };
+
+        let initial_collateral_token: Address = match Token::token_address_from_name(&raw.initial_collateral_token) {
+            Some(token_address) => parse_address("initial_collateral_token", &token_address)?,
+            None => parse_address("initial_collateral_token", &raw.initial_collateral_token)?,
+        };
+        let swap_path: Vec<Address> = raw.swap_path.iter()
+            .map(|market| parse_address("swap path", market))
+            .collect::<Result<Vec<_>, _>>()?;
+        let referral_code: H256 = raw.referral_code.parse::<H256>()
+            .map_err(|e| format!("Error converting referral code to H256: {}", e))?;
+
+        OrderObjectBuilder::new(raw.order_type)
+            .is_long(raw.is_long)
+            .position_asset(&raw.position_asset)
+            .amount(parse_u256("amount", &raw.amount)?)
+            .receiver(parse_address("receiver", &raw.receiver)?)
+            .callback_contract(parse_address("callback_contract", &raw.callback_contract)?)
+            .ui_fee_receiver(parse_address("ui_fee_receiver", &raw.ui_fee_receiver)?)
+            .market(parse_address("market", &raw.market)?)
+            .initial_collateral_token(initial_collateral_token)
+            .swap_path(swap_path)
+            .size_delta_usd(parse_u256("size_delta_usd", &raw.size_delta_usd)?)
+            .initial_collateral_delta_amount(parse_u256("initial_collateral_delta_amount", &raw.initial_collateral_delta_amount)?)
+            .trigger_price(parse_u256("trigger_price", &raw.trigger_price)?)
+            .acceptable_price(parse_u256("acceptable_price", &raw.acceptable_price)?)
+            .execution_fee(parse_u256("execution_fee", &raw.execution_fee)?)
+            .callback_gas_limit(parse_u256("callback_gas_limit", &raw.callback_gas_limit)?)
+            .min_output_amount(parse_u256("min_output_amount", &raw.min_output_amount)?)
+            .decrease_position_swap_type(raw.decrease_position_swap_type)
+            .should_unwrap_native_token(raw.should_unwrap_native_token)
+            .referral_code(referral_code)
+            .build()
+    }
+}
+
+impl From<&OrderObject> for RawOrderObject {
+    fn from(order: &OrderObject) -> Self {
+        // Debug formatting keeps the full hex, Display would abbreviate addresses
+        RawOrderObject {
+            is_long: order.is_long,
+            position_asset: order.position_asset.clone(),
+            amount: order.amount.to_string(),
+            receiver: format!("{:?}", order.receiver),
+            callback_contract: format!("{:?}", order.callback_contract),
+            ui_fee_receiver: format!("{:?}", order.ui_fee_receiver),
+            market: format!("{:?}", order.market),
+            initial_collateral_token: format!("{:?}", order.initial_collateral_token),
+            swap_path: order.swap_path.iter().map(|market| format!("{:?}", market)).collect(),
+            size_delta_usd: order.size_delta_usd.to_string(),
+            initial_collateral_delta_amount: order.initial_collateral_delta_amount.to_string(),
+            trigger_price: order.trigger_price.to_string(),
+            acceptable_price: order.acceptable_price.to_string(),
+            execution_fee: order.execution_fee.to_string(),
+            callback_gas_limit: order.callback_gas_limit.to_string(),
+            min_output_amount: order.min_output_amount.to_string(),
+            order_type: order.order_type,
+            decrease_position_swap_type: order.decrease_position_swap_type,
+            should_unwrap_native_token: order.should_unwrap_native_token,
+            referral_code: format!("{:?}", order.referral_code),
+        }
+    }
+}
+
+pub struct OrderObjectBuilder {
+    order: OrderObject,
+}
+
+impl OrderObjectBuilder {
+    pub fn new(order_type: OrderType) -> Self {
+        OrderObjectBuilder {
+            order: OrderObject {
+                is_long: true,
+                position_asset: String::new(),
+                amount: U256::zero(),
+                receiver: Address::zero(),
+                callback_contract: Address::zero(),
+                ui_fee_receiver: Address::zero(),
+                market: Address::zero(),
+                initial_collateral_token: Address::zero(),
+                swap_path: Vec::new(),
+                size_delta_usd: U256::zero(),
+                initial_collateral_delta_amount: U256::zero(),
+                trigger_price: U256::zero(),
+                acceptable_price: U256::zero(),
+                execution_fee: U256::zero(),
+                callback_gas_limit: U256::zero(),
+                min_output_amount: U256::zero(),
+                order_type,
+                decrease_position_swap_type: DecreasePositionSwapType::NoSwap,
+                should_unwrap_native_token: false,
+                referral_code: H256::zero(),
+            },
+        }
+    }
+
+    pub fn is_long(mut self, is_long: bool) -> Self { self.order.is_long = is_long; self }
+    pub fn position_asset(mut self, position_asset: &str) -> Self { self.order.position_asset = position_asset.to_string(); self }
+    pub fn amount(mut self, amount: U256) -> Self { self.order.amount = amount; self }
+    pub fn receiver(mut self, receiver: Address) -> Self { self.order.receiver = receiver; self }
+    pub fn callback_contract(mut self, callback_contract: Address) -> Self { self.order.callback_contract = callback_contract; self }
+    pub fn ui_fee_receiver(mut self, ui_fee_receiver: Address) -> Self { self.order.ui_fee_receiver = ui_fee_receiver; self }
+    pub fn market(mut self, market: Address) -> Self { self.order.market = market; self }
+    pub fn initial_collateral_token(mut self, token: Address) -> Self { self.order.initial_collateral_token = token; self }
+    pub fn swap_path(mut self, swap_path: Vec<Address>) -> Self { self.order.swap_path = swap_path; self }
+    pub fn size_delta_usd(mut self, size_delta_usd: U256) -> Self { self.order.size_delta_usd = size_delta_usd; self }
+    pub fn initial_collateral_delta_amount(mut self, amount: U256) -> Self { self.order.initial_collateral_delta_amount = amount; self }
+    pub fn trigger_price(mut self, trigger_price: U256) -> Self { self.order.trigger_price = trigger_price; self }
+    pub fn acceptable_price(mut self, acceptable_price: U256) -> Self { self.order.acceptable_price = acceptable_price; self }
+    pub fn execution_fee(mut self, execution_fee: U256) -> Self { self.order.execution_fee = execution_fee; self }
+    pub fn callback_gas_limit(mut self, callback_gas_limit: U256) -> Self { self.order.callback_gas_limit = callback_gas_limit; self }
+    pub fn min_output_amount(mut self, min_output_amount: U256) -> Self { self.order.min_output_amount = min_output_amount; self }
+    pub fn decrease_position_swap_type(mut self, swap_type: DecreasePositionSwapType) -> Self { self.order.decrease_position_swap_type = swap_type; self }
+    pub fn should_unwrap_native_token(mut self, should_unwrap: bool) -> Self { self.order.should_unwrap_native_token = should_unwrap; self }
+    pub fn referral_code(mut self, referral_code: H256) -> Self { self.order.referral_code = referral_code; self }
+
+    // Catches inconsistent orders before anything is signed or sent
+    pub fn build(self) -> Result<OrderObject, Box<dyn std::error::Error>> {
+        let order: OrderObject = self.order;
+
+        if order.receiver.is_zero() {
+            return Err("Order receiver must be set".into());
+        }
+        if order.execution_fee.is_zero() {
+            return Err("Order execution fee must be greater than zero".into());
+        }
+
+        let collateral_token: Token = Token::from_address(order.initial_collateral_token)
+            .ok_or("Initial collateral token is not a supported token")?;
+        let swap_markets: Vec<Markets> = order.swap_path.iter()
+            .map(|market| Markets::from_address(*market).ok_or(format!("Swap path entry {:?} is not a known market", market)))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        if order.order_type.is_swap() {
+            if !order.market.is_zero() {
+                return Err("Swap orders must not set a market".into());
+            }
+            if swap_markets.is_empty() {
+                return Err("Swap orders need at least one market in the swap path".into());
+            }
+            Markets::get_swap_output_token(collateral_token.info().name, &swap_markets)
+                .ok_or("Swap path does not route from the initial collateral token")?;
+            return Ok(order);
+        }
+
+        let market: Markets = Markets::from_address(order.market)
+            .ok_or("Order market is not a known market")?;
+        let market_info: MarketInfo = market.info();
+        if order.size_delta_usd.is_zero() && order.initial_collateral_delta_amount.is_zero() {
+            return Err("Position orders must change either size or collateral".into());
+        }
+        if matches!(order.order_type, OrderType::LimitIncrease | OrderType::LimitDecrease | OrderType::StopLossDecrease)
+            && order.trigger_price.is_zero() {
+            return Err("Limit and stop loss orders need a trigger price".into());
+        }
+
+        // Increases swap the initial collateral along the path before it lands in the position,
+        // decreases use the path for the payout, so their collateral must already belong to the market
+        let position_collateral: String = if order.order_type.is_increase() {
+            Markets::get_swap_output_token(collateral_token.info().name, &swap_markets)
+                .ok_or("Swap path does not route from the initial collateral token")?
+        } else {
+            collateral_token.info().name.to_string()
+        };
+        if position_collateral != market_info.long_token && position_collateral != market_info.short_token {
+            return Err(format!(
+                "Collateral {} is not a pool token of the {} market",
+                position_collateral, market_info.market
+            ).into());
+        }
+
+        Ok(order)
+    }
+}
+
 pub struct TokenInfo {
     pub name: &'static str,
     pub address: &'static str,
     pub decimals: u8,
 }
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum Token {
     ETH,
     BTC,
@@ -278,6 +653,36 @@ impl Token {
         }
     }
 
+    pub fn all() -> Vec<Token> {
+        vec![
+            Token::ETH,
+            Token::BTC,
+            Token::WBTC,
+            Token::LINK,
+            Token::ARB,
+            Token::SOL,
+            Token::UNI,
+            Token::XRP,
+            Token::LTC,
+            Token::DOGE,
+            Token::USDC,
+            Token::USDCE,
+            Token::DAI,
+            Token::USDT,
+        ]
+    }
+
+    pub fn from_address(address: Address) -> Option<Token> {
+        Token::all()
+            .into_iter()
+            .find(|token| token.info().address.parse::<Address>().ok() == Some(address))
+    }
+
+    // Synthetic index tokens only exist as price feeds, there is no ERC20 behind their address
+    pub fn is_synthetic(&self) -> bool {
+        matches!(self, Token::BTC | Token::XRP | Token::DOGE | Token::LTC)
+    }
+
     pub fn token_address_from_name(name: &str) -> Option<String> {
         Token::from_name(name)
             .map(|token| token.info().address.to_string())
@@ -290,13 +695,54 @@ pub struct SimpleOrder {
     pub collateral_amount: String,
     pub collateral_token: String,
     pub leverage_factor: f32,
+    pub slippage_bps: u32,
+}
+
+pub struct SimpleLimitOrder {
+    pub is_long: bool,
+    pub index_token: String,
+    pub collateral_amount: String,
+    pub collateral_token: String,
+    pub leverage_factor: f32,
+    pub trigger_price: String, // USD price of the index token, e.g. "42000.5"
+    pub slippage_bps: u32,
 }
 
 pub struct SimpleClosePosition {
+    pub is_long: bool,
     pub reciever: String,
     pub index_token: String,
     pub collateral_token: String,
+    pub slippage_bps: u32,
+    pub should_unwrap_native_token: bool, // Pays out native ETH instead of WETH when the collateral is ETH
+}
 
+pub struct SimpleTriggerDecrease {
+    pub is_long: bool,
+    pub index_token: String,
+    pub collateral_token: String,
+    pub trigger_price: String, // USD price of the index token, e.g. "42000.5"
+    pub size_delta_usd: Option<String>, // 30 decimal USD, None closes the full position
+    pub collateral_withdrawal_amount: String, // Collateral token units
+    pub slippage_bps: u32,
+    pub should_unwrap_native_token: bool, // Pays out native ETH instead of WETH when the collateral is ETH
+}
+
+pub struct SimpleSwap {
+    pub from_token: String,
+    pub to_token: String,
+    pub amount: String, // From token units
+    pub slippage_bps: u32,
+    pub limit_output_amount: Option<String>, // To token units, Some makes this a LimitSwap
+}
+
+// Notional of an order before it is sent, all USD values in GMX's 30 decimal precision
+#[derive(Debug, Clone)]
+pub struct PositionSizing {
+    pub collateral_amount: U256,
+    pub collateral_price: U256,
+    pub collateral_value_usd: U256,
+    pub size_delta_usd: U256,
 }
 
 #[derive(Debug)]
@@ -313,6 +759,7 @@ pub struct MarketIncreaseOrderCalcOutput {
 
 #[derive(Debug)]
 pub struct MarketDecreaseOrderCalcOutput {
+    pub is_long: bool,
     pub size_delta_usd: U256,
     pub initial_collateral_delta_amount: U256,
     pub trigger_price: U256,
@@ -320,6 +767,7 @@ pub struct MarketDecreaseOrderCalcOutput {
     pub execution_fee: U256,
     pub callback_gas_limit: U256,
     pub min_output_amount: U256,
+    pub should_unwrap_native_token: bool,
 }
 
 #[derive(Debug, Clone)]
@@ -333,6 +781,412 @@ pub struct AddressesForMarketIncreaseOrder {
     pub referral_code: String, 
 }
 
+#[derive(Debug)]
+pub struct SwapOrderCalcOutput {
+    pub amount: U256,
+    pub min_output_amount: U256,
+    pub execution_fee: U256,
+    pub order_type: OrderType,
+}
+
+#[derive(Debug, Clone)]
+pub struct AddressesForSwapOrder {
+    pub receiver: String,
+    pub callback_contract: String,
+    pub ui_fee_receiver: String,
+    pub initial_collateral_token: String,
+    pub swap_path: Vec<String>,
+    pub referral_code: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct AddressesForMarketDecreaseOrder {
+    pub receiver: String,
+    pub callback_contract: String,
+    pub ui_fee_receiver: String,
+    pub market: String,
+    pub initial_collateral_token: String,
+    pub swap_path: Vec<String>,
+    pub referral_code: String,
+}
+
+// Where a submitted order ended up once a keeper has acted on it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OrderStatus {
+    Executed,
+    Cancelled { reason: String },
+    Frozen { reason: String },
+}
+
+// Router approval for an order's collateral, built without a provider so it can be reviewed before sending
+#[derive(Debug, Clone)]
+pub struct ApprovalRequest {
+    pub token: Token,
+    pub required_amount: U256,
+    pub approve_amount: U256,
+    pub transaction: TypedTransaction,
+}
+
+// One call inside the ExchangeRouter multicall, kept decoded alongside the encoded transaction
+#[derive(Debug, Clone)]
+pub enum BundledCall {
+    SendWnt(SendWntCall),
+    SendTokens(SendTokensCall),
+    // Boxed, the order params are several times the size of the other calls
+    CreateOrder(Box<CreateOrderCall>),
+}
+
+// Everything sol_call would send for an order. Fees, gas and nonces are filled in by the send phase.
+#[derive(Debug, Clone)]
+pub struct OrderTransactionBundle {
+    pub approval: Option<ApprovalRequest>,
+    pub calls: Vec<BundledCall>,
+    pub multicall_transaction: TypedTransaction,
+}
+
+// What sol_call sent and what it cost, for callers to persist or act on
+#[derive(Debug, Clone)]
+pub struct OrderSubmission {
+    pub order_key: H256,
+    pub approval_tx_hash: Option<H256>,
+    pub order_tx_hash: H256,
+    pub block_number: U64,
+    pub gas_used: U256,
+    pub effective_gas_price: U256,
+    pub total_cost_wei: U256, // Gas for the approval and order transactions plus the execution fee
+    pub total_cost_eth: String,
+}
+
+// Per-transaction submission settings shared by every send path
+#[derive(Debug, Clone)]
+pub struct TransactionSettings {
+    pub simulate: bool, // Runs the transaction as an eth_call first and decodes any GMX error
+    pub gas_buffer_bps: u32, // Added on top of eth_estimateGas, 2000 = +20%
+    pub fee_strategy: FeeStrategy,
+    pub max_transaction_fee: Option<U256>, // Wei, refuses to send if gas limit * max fee per gas would exceed it
+}
+
+impl Default for TransactionSettings {
+    fn default() -> Self {
+        TransactionSettings {
+            simulate: true,
+            gas_buffer_bps: 2000,
+            fee_strategy: FeeStrategy::default(),
+            max_transaction_fee: None,
+        }
+    }
+}
+
+// How max_fee_per_gas and max_priority_fee_per_gas are chosen for each transaction
+#[derive(Debug, Clone, PartialEq)]
+pub enum FeeStrategy {
+    // Tip at the given reward percentile over recent blocks, max fee at a multiple of the next base fee
+    FeeHistory { block_count: u64, reward_percentile: f64, base_fee_multiplier_bps: u32 },
+    // Exactly these fees, regardless of network conditions
+    Fixed { max_fee_per_gas: U256, max_priority_fee_per_gas: U256 },
+    // eth_gasPrice plus the default tip, never above the cap
+    CappedMax { max_fee_per_gas: U256 },
+}
+
+impl Default for FeeStrategy {
+    fn default() -> Self {
+        FeeStrategy::FeeHistory {
+            block_count: 10,
+            reward_percentile: 50.0,
+            base_fee_multiplier_bps: 20000,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Eip1559Fees {
+    pub max_fee_per_gas: U256,
+    pub max_priority_fee_per_gas: U256,
+}
+
+// Raised when a transaction fails simulation or eth_estimateGas, before anything is signed or paid for
+#[derive(Debug, Clone)]
+pub enum PreflightError {
+    Reverted { error: GmxError, revert_data: Bytes },
+    Rpc(String),
+}
+
+impl std::fmt::Display for PreflightError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PreflightError::Reverted { error, .. } => write!(f, "Transaction would revert: {}", error),
+            PreflightError::Rpc(message) => write!(f, "Pre-flight check failed: {}", message),
+        }
+    }
+}
+
+// Why a GMX call reverted, decoded from its revert data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GmxError {
+    InsufficientCollateralAmount { collateral_amount: U256, collateral_delta_amount: I256 },
+    InsufficientCollateralUsd { remaining_collateral_usd: I256 },
+    OrderNotFulfillableAtAcceptablePrice { price: U256, acceptable_price: U256 },
+    DisabledMarket { market: Address },
+    EmptyMarket,
+    InsufficientWntAmountForExecutionFee { wnt_amount: U256, execution_fee: U256 },
+    InsufficientExecutionFee { min_execution_fee: U256, execution_fee: U256 },
+    // Raised at the end of every simulateExecuteOrder call, the simulated execution itself succeeded
+    EndOfOracleSimulation,
+    // Any other custom error declared in the crate's ABIs
+    Custom { name: String, args: Vec<String> },
+    // Plain revert message or compiler panic
+    Revert(String),
+    // Selector not declared in any of the crate's ABIs
+    Unknown { selector: [u8; 4] },
+}
+
+impl std::fmt::Display for GmxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GmxError::InsufficientCollateralAmount { collateral_amount, collateral_delta_amount } =>
+                write!(f, "InsufficientCollateralAmount: collateral {} cannot cover a delta of {}", collateral_amount, collateral_delta_amount),
+            GmxError::InsufficientCollateralUsd { remaining_collateral_usd } =>
+                write!(f, "InsufficientCollateralUsd: remaining collateral would be {} USD", remaining_collateral_usd),
+            GmxError::OrderNotFulfillableAtAcceptablePrice { price, acceptable_price } =>
+                write!(f, "OrderNotFulfillableAtAcceptablePrice: price {} is outside the acceptable price {}", price, acceptable_price),
+            GmxError::DisabledMarket { market } => write!(f, "DisabledMarket: {:?}", market),
+            GmxError::EmptyMarket => write!(f, "EmptyMarket: the order has no market"),
+            GmxError::InsufficientWntAmountForExecutionFee { wnt_amount, execution_fee } =>
+                write!(f, "InsufficientWntAmountForExecutionFee: sent {} for an execution fee of {}", wnt_amount, execution_fee),
+            GmxError::InsufficientExecutionFee { min_execution_fee, execution_fee } =>
+                write!(f, "InsufficientExecutionFee: {} is below the minimum {}", execution_fee, min_execution_fee),
+            GmxError::EndOfOracleSimulation => write!(f, "EndOfOracleSimulation: the simulated execution completed"),
+            GmxError::Custom { name, args } => write!(f, "{}({})", name, args.join(", ")),
+            GmxError::Revert(reason) => write!(f, "{}", reason),
+            GmxError::Unknown { selector } => write!(f, "unknown custom error 0x{}", hex::encode(selector)),
+        }
+    }
+}
+
+impl std::error::Error for PreflightError {}
+
+// An open position as stored by GMX. USD values use 30 decimals, token amounts use the token's own decimals.
+#[derive(Debug, Clone)]
+pub struct Position {
+    pub key: H256,
+    pub account: Address,
+    pub market: Address,
+    pub collateral_token: Address,
+    pub is_long: bool,
+    pub size_in_usd: U256,
+    pub size_in_tokens: U256,
+    pub collateral_amount: U256,
+    // Cumulative borrowing factor at the last update, pending borrowing fees accrue against the market's current factor
+    pub borrowing_factor: U256,
+    pub funding_fee_amount_per_size: U256,
+    pub long_token_claimable_funding_amount_per_size: U256,
+    pub short_token_claimable_funding_amount_per_size: U256,
+    pub increased_at_block: U256,
+    pub decreased_at_block: U256,
+}
+
+impl From<PositionProps> for Position {
+    fn from(props: PositionProps) -> Self {
+        Position {
+            key: get_position_key(props.addresses.account, props.addresses.market, props.addresses.collateral_token, props.flags.is_long),
+            account: props.addresses.account,
+            market: props.addresses.market,
+            collateral_token: props.addresses.collateral_token,
+            is_long: props.flags.is_long,
+            size_in_usd: props.numbers.size_in_usd,
+            size_in_tokens: props.numbers.size_in_tokens,
+            collateral_amount: props.numbers.collateral_amount,
+            borrowing_factor: props.numbers.borrowing_factor,
+            funding_fee_amount_per_size: props.numbers.funding_fee_amount_per_size,
+            long_token_claimable_funding_amount_per_size: props.numbers.long_token_claimable_funding_amount_per_size,
+            short_token_claimable_funding_amount_per_size: props.numbers.short_token_claimable_funding_amount_per_size,
+            increased_at_block: props.numbers.increased_at_block,
+            decreased_at_block: props.numbers.decreased_at_block,
+        }
+    }
+}
+
+// What closing the whole position would pay right now. Amounts are in the collateral token, USD values use 30 decimals.
+#[derive(Debug, Clone)]
+pub struct PositionFeeBreakdown {
+    pub borrowing_fee_amount: U256,
+    pub borrowing_fee_usd: U256,
+    pub funding_fee_amount: U256,
+    pub funding_fee_usd: U256,
+    pub claimable_long_token_amount: U256,
+    pub claimable_short_token_amount: U256,
+    pub position_fee_amount: U256,
+    pub position_fee_usd: U256,
+    pub ui_fee_amount: U256,
+    pub referral_discount_amount: U256,
+    pub total_cost_amount: U256,
+    pub total_cost_usd: U256,
+}
+
+// A position valued at current oracle prices
+#[derive(Debug, Clone)]
+pub struct PositionInfo {
+    pub position: Position,
+    pub collateral_token_price: U256,
+    pub collateral_usd: U256,
+    pub base_pnl_usd: I256,
+    pub uncapped_base_pnl_usd: I256,
+    pub price_impact_usd: I256,
+    pub pnl_after_price_impact_usd: I256,
+    pub execution_price: U256,
+    pub fees: PositionFeeBreakdown,
+    // Collateral plus PnL after price impact, less every fee a close would pay
+    pub net_value_usd: I256,
+}
+
+impl From<ReaderPositionInfo> for PositionInfo {
+    fn from(info: ReaderPositionInfo) -> Self {
+        // Fee amounts are charged in the collateral token and valued at its min price, as the contracts do
+        let collateral_token_price: U256 = info.fees.collateral_token_price.min;
+        let to_usd = |amount: U256| amount * collateral_token_price;
+
+        let fees: PositionFeeBreakdown = PositionFeeBreakdown {
+            borrowing_fee_amount: info.fees.borrowing.borrowing_fee_amount,
+            borrowing_fee_usd: info.fees.borrowing.borrowing_fee_usd,
+            funding_fee_amount: info.fees.funding.funding_fee_amount,
+            funding_fee_usd: to_usd(info.fees.funding.funding_fee_amount),
+            claimable_long_token_amount: info.fees.funding.claimable_long_token_amount,
+            claimable_short_token_amount: info.fees.funding.claimable_short_token_amount,
+            position_fee_amount: info.fees.position_fee_amount,
+            position_fee_usd: to_usd(info.fees.position_fee_amount),
+            ui_fee_amount: info.fees.ui.ui_fee_amount,
+            referral_discount_amount: info.fees.referral.trader_discount_amount,
+            total_cost_amount: info.fees.total_cost_amount,
+            total_cost_usd: to_usd(info.fees.total_cost_amount),
+        };
+
+        let collateral_usd: U256 = to_usd(info.position.numbers.collateral_amount);
+        let net_value_usd: I256 = I256::from_raw(collateral_usd) + info.pnl_after_price_impact_usd - I256::from_raw(fees.total_cost_usd);
+
+        PositionInfo {
+            position: Position::from(info.position),
+            collateral_token_price,
+            collateral_usd,
+            base_pnl_usd: info.base_pnl_usd,
+            uncapped_base_pnl_usd: info.uncapped_base_pnl_usd,
+            price_impact_usd: info.execution_price_result.price_impact_usd,
+            pnl_after_price_impact_usd: info.pnl_after_price_impact_usd,
+            execution_price: info.execution_price_result.execution_price,
+            fees,
+            net_value_usd,
+        }
+    }
+}
+
+// Everything the liquidation check depends on. USD values use 30 decimals, prices are per token unit in contract precision.
+#[derive(Debug, Clone)]
+pub struct LiquidationInputs {
+    pub is_long: bool,
+    pub size_in_usd: U256,
+    pub size_in_tokens: U256,
+    // When the collateral is the index token its value moves with the price, so the amount is used instead of the USD value
+    pub collateral_is_index_token: bool,
+    pub collateral_amount: U256,
+    pub collateral_usd: U256,
+    // Pending borrowing and funding plus the fees the close itself would pay
+    pub pending_fees_usd: U256,
+    // Price impact of closing the whole position. Only a negative impact counts, as in the contracts.
+    pub close_price_impact_usd: I256,
+    pub min_collateral_factor: U256,
+    pub min_collateral_usd: U256,
+}
+
+// A resting order as stored by GMX, with its type, market and collateral decoded.
+// Prices are in contract precision and USD values use 30 decimals.
+#[derive(Debug, Clone)]
+pub struct Order {
+    pub key: H256,
+    pub account: Address,
+    pub receiver: Address,
+    pub order_type: OrderType,
+    pub decrease_position_swap_type: DecreasePositionSwapType,
+    // None for swap orders, which have no position market, or for markets the crate does not list
+    pub market: Option<Markets>,
+    pub market_address: Address,
+    pub collateral_token: Option<Token>,
+    pub initial_collateral_token: Address,
+    pub swap_path: Vec<Address>,
+    pub is_long: bool,
+    pub size_delta_usd: U256,
+    pub initial_collateral_delta_amount: U256,
+    pub trigger_price: U256,
+    pub acceptable_price: U256,
+    pub execution_fee: U256,
+    pub callback_gas_limit: U256,
+    pub min_output_amount: U256,
+    pub should_unwrap_native_token: bool,
+    pub is_frozen: bool,
+    pub updated_at_block: U256,
+}
+
+impl Order {
+    // The Reader returns orders without their keys, so the key the order was read under is passed alongside
+    pub fn from_props(key: H256, props: OrderProps) -> Result<Self, Box<dyn std::error::Error>> {
+        Ok(Order {
+            key,
+            account: props.addresses.account,
+            receiver: props.addresses.receiver,
+            order_type: OrderType::try_from(props.numbers.order_type)?,
+            decrease_position_swap_type: DecreasePositionSwapType::try_from(props.numbers.decrease_position_swap_type)?,
+            market: Markets::from_address(props.addresses.market),
+            market_address: props.addresses.market,
+            collateral_token: Token::from_address(props.addresses.initial_collateral_token),
+            initial_collateral_token: props.addresses.initial_collateral_token,
+            swap_path: props.addresses.swap_path,
+            is_long: props.flags.is_long,
+            size_delta_usd: props.numbers.size_delta_usd,
+            initial_collateral_delta_amount: props.numbers.initial_collateral_delta_amount,
+            trigger_price: props.numbers.trigger_price,
+            acceptable_price: props.numbers.acceptable_price,
+            execution_fee: props.numbers.execution_fee,
+            callback_gas_limit: props.numbers.callback_gas_limit,
+            min_output_amount: props.numbers.min_output_amount,
+            should_unwrap_native_token: props.flags.should_unwrap_native_token,
+            is_frozen: props.flags.is_frozen,
+            updated_at_block: props.numbers.updated_at_block,
+        })
+    }
+}
+
+// Market conditions at one block. Pool amounts are in each token's own decimals, open interest in 30 decimal USD,
+// and the borrowing and funding factors are per second with 30 decimals.
+#[derive(Debug, Clone)]
+pub struct MarketSnapshot {
+    pub market: Markets,
+    pub market_token: Address,
+    pub index_token: Address,
+    pub long_token: Address,
+    pub short_token: Address,
+    pub long_pool_amount: U256,
+    pub short_pool_amount: U256,
+    pub long_open_interest_usd: U256,
+    pub short_open_interest_usd: U256,
+    pub borrowing_factor_per_second_for_longs: U256,
+    pub borrowing_factor_per_second_for_shorts: U256,
+    pub longs_pay_shorts: bool,
+    pub funding_factor_per_second: U256,
+    pub next_saved_funding_factor_per_second: I256,
+    pub virtual_pool_amount_for_long_token: U256,
+    pub virtual_pool_amount_for_short_token: U256,
+    pub virtual_inventory_for_positions: I256,
+    pub is_disabled: bool,
+}
+
+// Orders confirmed cancelled by OrderCancelled events, with the execution fee GMX returned to the account for them.
+// Market orders still inside the request expiration window cannot be cancelled yet and are listed in skipped_order_keys.
+#[derive(Debug, Clone)]
+pub struct CancelledOrders {
+    pub order_keys: Vec<H256>,
+    pub skipped_order_keys: Vec<H256>,
+    pub refunded_execution_fee: U256,
+    pub receipt: Option<TransactionReceipt>,
+}
+
 #[derive(Deserialize, Debug)]
 pub struct PriceData {
     pub id: String,
@@ -398,131 +1252,131 @@ pub struct GasPriceResponse {
 }
 
 pub struct KeyHashes {
-    wnt: String,
-    nonce: String,
-    fee_receiver: String,
-    holding_address: String,
-    in_strict_price_feed_mode: String,
-    min_handle_execution_error_gas: String,
-    min_additional_gas_for_execution: String,
-    min_handle_execution_error_gas_to_forward: String,
-    max_leverage: String,
-    market_list: String,
-    deposit_list: String,
-    account_deposit_list: String,
-    withdrawal_list: String,
-    account_withdrawal_list: String,
-    position_list: String,
-    account_position_list: String,
-    order_list: String,
-    account_order_list: String,
-    subaccount_list: String,
-    create_deposit_feature_disabled: String,
-    cancel_deposit_feature_disabled: String,
-    execute_deposit_feature_disabled: String,
-    create_order_feature_disabled: String,
-    execute_order_feature_disabled: String,
-    execute_adl_feature_disabled: String,
-    update_order_feature_disabled: String,
-    cancel_order_feature_disabled: String,
-    claimable_fee_amount: String,
-    claimable_funding_amount: String,
-    claimable_collateral_amount: String,
-    claimable_collateral_factor: String,
-    claimable_collateral_time_divisor: String,
-    claimable_ui_fee_amount: String,
-    affiliate_reward: String,
-    max_ui_fee_factor: String,
-    is_market_disabled: String,
-    max_swap_path_length: String,
-    min_market_tokens_for_first_deposit: String,
-    min_oracle_block_confirmations: String,
-    max_oracle_price_age: String,
-    max_oracle_ref_price_deviation_factor: String,
-    min_oracle_signers: String,
-    min_collateral_factor: String,
-    min_collateral_factor_for_open_interest_multiplier: String,
-    min_collateral_usd: String,
-    min_position_size_usd: String,
-    swap_fee_receiver_factor: String,
-    token_transfer_gas_limit: String,
-    native_token_transfer_gas_limit: String,
-    max_callback_gas_limit: String,
-    request_expiration_block_age: String,
-    price_feed: String,
-    price_feed_multiplier: String,
-    price_feed_heartbeat_duration: String,
-    realtime_feed_id: String,
-    realtime_feed_multiplier: String,
-    stable_price: String,
-    oracle_type: String,
-    open_interest: String,
-    open_interest_in_tokens: String,
-    collateral_sum: String,
-    pool_amount: String,
-    max_pool_amount: String,
-    max_pool_amount_for_deposit: String,
-    max_open_interest: String,
-    position_impact_pool_amount: String,
-    min_position_impact_pool_amount: String,
-    position_impact_pool_distribution_rate: String,
-    position_impact_pool_distributed_at: String,
-    swap_impact_pool_amount: String,
-    position_fee_receiver_factor: String,
-    borrowing_fee_receiver_factor: String,
-    swap_fee_factor: String,
-    swap_impact_factor: String,
-    swap_impact_exponent_factor: String,
-    position_impact_factor: String,
-    position_impact_exponent_factor: String,
-    max_position_impact_factor: String,
-    max_position_impact_factor_for_liquidations: String,
-    position_fee_factor: String,
-    reserve_factor: String,
-    open_interest_reserve_factor: String,
-    max_pnl_factor: String,
-    max_pnl_factor_for_traders: String,
-    max_pnl_factor_for_adl: String,
-    min_pnl_factor_after_adl: String,
-    max_pnl_factor_for_deposits: String,
-    max_pnl_factor_for_withdrawals: String,
-    latest_adl_block: String,
-    is_adl_enabled: String,
-    funding_factor: String,
-    funding_exponent_factor: String,
-    saved_funding_factor_per_second: String,
-    funding_increase_factor_per_second: String,
-    funding_decrease_factor_per_second: String,
-    min_funding_factor_per_second: String,
-    max_funding_factor_per_second: String,
-    threshold_for_stable_funding: String,
-    threshold_for_decrease_funding: String,
-    funding_fee_amount_per_size: String,
-    claimable_funding_amount_per_size: String,
-    funding_updated_at: String,
-    borrowing_factor: String,
-    borrowing_exponent_factor: String,
-    skip_borrowing_fee_for_smaller_side: String,
-    estimated_gas_fee_base_amount: String,
-    estimated_gas_fee_multiplier_factor: String,
-    execution_gas_fee_base_amount: String,
-    execution_gas_fee_multiplier_factor: String,
-    deposit_gas_limit: String,
-    withdrawal_gas_limit: String,
-    single_swap_gas_limit: String,
-    increase_order_gas_limit: String,
-    decrease_order_gas_limit: String,
-    swap_order_gas_limit: String,
-    cumulative_borrowing_factor: String,
-    cumulative_borrowing_factor_updated_at: String,
-    virtual_token_id: String,
-    virtual_market_id: String,
-    virtual_inventory_for_swaps: String,
-    virtual_inventory_for_positions: String,
-    max_allowed_subaccount_action_count: String,
-    subaccount_action_count: String,
-    subaccount_auto_top_up_amount: String,
-    subaccount_order_action: String,
+    pub wnt: String,
+    pub nonce: String,
+    pub fee_receiver: String,
+    pub holding_address: String,
+    pub in_strict_price_feed_mode: String,
+    pub min_handle_execution_error_gas: String,
+    pub min_additional_gas_for_execution: String,
+    pub min_handle_execution_error_gas_to_forward: String,
+    pub max_leverage: String,
+    pub market_list: String,
+    pub deposit_list: String,
+    pub account_deposit_list: String,
+    pub withdrawal_list: String,
+    pub account_withdrawal_list: String,
+    pub position_list: String,
+    pub account_position_list: String,
+    pub order_list: String,
+    pub account_order_list: String,
+    pub subaccount_list: String,
+    pub create_deposit_feature_disabled: String,
+    pub cancel_deposit_feature_disabled: String,
+    pub execute_deposit_feature_disabled: String,
+    pub create_order_feature_disabled: String,
+    pub execute_order_feature_disabled: String,
+    pub execute_adl_feature_disabled: String,
+    pub update_order_feature_disabled: String,
+    pub cancel_order_feature_disabled: String,
+    pub claimable_fee_amount: String,
+    pub claimable_funding_amount: String,
+    pub claimable_collateral_amount: String,
+    pub claimable_collateral_factor: String,
+    pub claimable_collateral_time_divisor: String,
+    pub claimable_ui_fee_amount: String,
+    pub affiliate_reward: String,
+    pub max_ui_fee_factor: String,
+    pub is_market_disabled: String,
+    pub max_swap_path_length: String,
+    pub min_market_tokens_for_first_deposit: String,
+    pub min_oracle_block_confirmations: String,
+    pub max_oracle_price_age: String,
+    pub max_oracle_ref_price_deviation_factor: String,
+    pub min_oracle_signers: String,
+    pub min_collateral_factor: String,
+    pub min_collateral_factor_for_open_interest_multiplier: String,
+    pub min_collateral_usd: String,
+    pub min_position_size_usd: String,
+    pub swap_fee_receiver_factor: String,
+    pub token_transfer_gas_limit: String,
+    pub native_token_transfer_gas_limit: String,
+    pub max_callback_gas_limit: String,
+    pub request_expiration_block_age: String,
+    pub price_feed: String,
+    pub price_feed_multiplier: String,
+    pub price_feed_heartbeat_duration: String,
+    pub realtime_feed_id: String,
+    pub realtime_feed_multiplier: String,
+    pub stable_price: String,
+    pub oracle_type: String,
+    pub open_interest: String,
+    pub open_interest_in_tokens: String,
+    pub collateral_sum: String,
+    pub pool_amount: String,
+    pub max_pool_amount: String,
+    pub max_pool_amount_for_deposit: String,
+    pub max_open_interest: String,
+    pub position_impact_pool_amount: String,
+    pub min_position_impact_pool_amount: String,
+    pub position_impact_pool_distribution_rate: String,
+    pub position_impact_pool_distributed_at: String,
+    pub swap_impact_pool_amount: String,
+    pub position_fee_receiver_factor: String,
+    pub borrowing_fee_receiver_factor: String,
+    pub swap_fee_factor: String,
+    pub swap_impact_factor: String,
+    pub swap_impact_exponent_factor: String,
+    pub position_impact_factor: String,
+    pub position_impact_exponent_factor: String,
+    pub max_position_impact_factor: String,
+    pub max_position_impact_factor_for_liquidations: String,
+    pub position_fee_factor: String,
+    pub reserve_factor: String,
+    pub open_interest_reserve_factor: String,
+    pub max_pnl_factor: String,
+    pub max_pnl_factor_for_traders: String,
+    pub max_pnl_factor_for_adl: String,
+    pub min_pnl_factor_after_adl: String,
+    pub max_pnl_factor_for_deposits: String,
+    pub max_pnl_factor_for_withdrawals: String,
+    pub latest_adl_block: String,
+    pub is_adl_enabled: String,
+    pub funding_factor: String,
+    pub funding_exponent_factor: String,
+    pub saved_funding_factor_per_second: String,
+    pub funding_increase_factor_per_second: String,
+    pub funding_decrease_factor_per_second: String,
+    pub min_funding_factor_per_second: String,
+    pub max_funding_factor_per_second: String,
+    pub threshold_for_stable_funding: String,
+    pub threshold_for_decrease_funding: String,
+    pub funding_fee_amount_per_size: String,
+    pub claimable_funding_amount_per_size: String,
+    pub funding_updated_at: String,
+    pub borrowing_factor: String,
+    pub borrowing_exponent_factor: String,
+    pub skip_borrowing_fee_for_smaller_side: String,
+    pub estimated_gas_fee_base_amount: String,
+    pub estimated_gas_fee_multiplier_factor: String,
+    pub execution_gas_fee_base_amount: String,
+    pub execution_gas_fee_multiplier_factor: String,
+    pub deposit_gas_limit: String,
+    pub withdrawal_gas_limit: String,
+    pub single_swap_gas_limit: String,
+    pub increase_order_gas_limit: String,
+    pub decrease_order_gas_limit: String,
+    pub swap_order_gas_limit: String,
+    pub cumulative_borrowing_factor: String,
+    pub cumulative_borrowing_factor_updated_at: String,
+    pub virtual_token_id: String,
+    pub virtual_market_id: String,
+    pub virtual_inventory_for_swaps: String,
+    pub virtual_inventory_for_positions: String,
+    pub max_allowed_subaccount_action_count: String,
+    pub subaccount_action_count: String,
+    pub subaccount_auto_top_up_amount: String,
+    pub subaccount_order_action: String,
 }
 
 impl KeyHashes {
@@ -0,0 +1,116 @@
+use ethers::prelude::*;
+use ethers::types::transaction::eip2718::TypedTransaction;
+use ethers::types::{H160, U256, NameOrAddress};
+use std::sync::Arc;
+
+use crate::contract_caller::utils::constants::BPS_DIVISOR;
+use crate::contract_caller::utils::contract_addresses::ARBITRUM_CHAIN_ID;
+use crate::contract_caller::utils::gas_calculator::resolve_eip1559_fees;
+use crate::contract_caller::utils::nonce_manager::{allocate_nonce, resync_nonce};
+use crate::contract_caller::utils::gmx_errors::decode_gmx_error;
+use crate::contract_caller::utils::structs::{Eip1559Fees, PreflightError, TransactionSettings};
+
+pub type SignerClient = SignerMiddleware<Arc<Provider<Http>>, LocalWallet>;
+
+// Sends a plain call from the client's wallet, see send_typed_transaction
+pub async fn send_eip1559_transaction(
+    client: &SignerClient,
+    to: H160,
+    data: Bytes,
+    value: U256,
+    settings: &TransactionSettings,
+) -> Result<Option<TransactionReceipt>, Box<dyn std::error::Error>> {
+    let typed_tx: TypedTransaction = TypedTransaction::Eip1559(Eip1559TransactionRequest {
+        from: Some(client.address()),
+        to: Some(NameOrAddress::Address(to)),
+        value: Some(value),
+        data: Some(data),
+        chain_id: Some(ARBITRUM_CHAIN_ID.into()),
+        ..Default::default()
+    });
+
+    send_typed_transaction(client, typed_tx, settings).await
+}
+
+// Fills in fees, gas and nonce, then signs and submits the transaction, waiting for one confirmation.
+// A transaction that would revert is caught by simulation or estimation and returned as a PreflightError without being sent.
+// Nonces come from the shared nonce manager, so several order tasks can submit from one wallet at once.
+pub async fn send_typed_transaction(
+    client: &SignerClient,
+    mut typed_tx: TypedTransaction,
+    settings: &TransactionSettings,
+) -> Result<Option<TransactionReceipt>, Box<dyn std::error::Error>> {
+    let from: H160 = client.address();
+    typed_tx.set_from(from);
+
+    // Step 1: Price the Transaction
+    let fees: Eip1559Fees = resolve_eip1559_fees(client, &settings.fee_strategy).await?;
+    if let TypedTransaction::Eip1559(eip1559_tx) = &mut typed_tx {
+        eip1559_tx.max_fee_per_gas = Some(fees.max_fee_per_gas);
+        eip1559_tx.max_priority_fee_per_gas = Some(fees.max_priority_fee_per_gas);
+    } else {
+        return Err("Only EIP-1559 transactions are supported".into());
+    }
+
+    // Step 2: Simulate and estimate gas against the real calldata and value
+    if settings.simulate {
+        simulate_transaction(client, &typed_tx).await?;
+    }
+    let gas_limit: U256 = estimate_gas_with_buffer(client, &typed_tx, settings.gas_buffer_bps).await?;
+    typed_tx.set_gas(gas_limit);
+
+    // Worst case cost is the full gas limit at the max fee, refuse to send anything that could exceed the ceiling
+    if let Some(max_transaction_fee) = settings.max_transaction_fee {
+        let worst_case_fee: U256 = gas_limit.checked_mul(fees.max_fee_per_gas).ok_or("Transaction fee overflow")?;
+        if worst_case_fee > max_transaction_fee {
+            return Err(format!("Transaction fee of up to {} wei exceeds the ceiling of {} wei", worst_case_fee, max_transaction_fee).into());
+        }
+    }
+
+    // Step 3: Sign and Send the Transaction. The nonce is only allocated once every pre-flight check
+    // has passed, so a transaction rejected before sending never leaves a gap.
+    let nonce: U256 = allocate_nonce(client, from).await?;
+    typed_tx.set_nonce(nonce);
+
+    let result: Result<Option<TransactionReceipt>, Box<dyn std::error::Error>> = async {
+        let pending_tx: PendingTransaction<'_, Http> = client.send_transaction(typed_tx, None).await?;
+        let receipt: Option<TransactionReceipt> = pending_tx.confirmations(1).await?;
+        Ok(receipt)
+    }.await;
+
+    // A failed send or dropped transaction leaves its nonce unused and later ones would stall behind it
+    if !matches!(result, Ok(Some(_))) {
+        resync_nonce(client, from).await?;
+    }
+
+    result
+}
+
+pub async fn estimate_gas_with_buffer(client: &SignerClient, tx: &TypedTransaction, gas_buffer_bps: u32) -> Result<U256, PreflightError> {
+    let gas_estimate: U256 = client.estimate_gas(tx, None).await
+    .map_err(|e| to_preflight_error(&e))?;
+
+    let gas_limit: U256 = gas_estimate * U256::from(BPS_DIVISOR + gas_buffer_bps) / U256::from(BPS_DIVISOR);
+    println!("Estimated Gas: {}, GAS LIMIT = {}", gas_estimate, gas_limit);
+
+    Ok(gas_limit)
+}
+
+// Runs the transaction as an eth_call against the latest block, returning its output or the decoded GMX error
+pub async fn simulate_transaction(client: &SignerClient, tx: &TypedTransaction) -> Result<Bytes, PreflightError> {
+    let output: Bytes = client.call(tx, Some(BlockId::Number(BlockNumber::Latest))).await
+    .map_err(|e| to_preflight_error(&e))?;
+    println!("Simulation ok");
+
+    Ok(output)
+}
+
+pub fn to_preflight_error<E: MiddlewareError>(error: &E) -> PreflightError {
+    match error.as_error_response().and_then(|rpc_error| rpc_error.as_revert_data()) {
+        Some(revert_data) => PreflightError::Reverted {
+            error: decode_gmx_error(&revert_data),
+            revert_data,
+        },
+        None => PreflightError::Rpc(error.to_string()),
+    }
+}
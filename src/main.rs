@@ -40,12 +40,13 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         collateral_amount: "2053551709838329".to_string(),
         index_token: "WBTC".to_string(),
         leverage_factor: 10.0,
+        slippage_bps: 30,
     };
 
     let test_result: OrderObject = get_order_object_from_simple_order(&test).await?;
-    let receipt = sol_call(test_result).await?;
+    let submission = sol_call(test_result).await?;
 
-    println!("{:?}", receipt);
+    println!("{:?}", submission);
 
     Ok(())
 }
\ No newline at end of file